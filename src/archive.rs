@@ -1,204 +1,920 @@
+use crate::divergence::Divergence;
+use crate::entry::Entries;
+use crate::entry::Source;
+use crate::error::Error;
+use crate::error::Result;
 use crate::header::BLOCK_SIZE;
+use crate::header::Checksum;
+use crate::header::DEVMAJOR_RANGE;
+use crate::header::DEVMINOR_RANGE;
 use crate::header::Format;
+use crate::header::LINKNAME_RANGE;
 use crate::header::LintHeader;
+use crate::header::MAGIC_RANGE;
+use crate::header::NAME_RANGE;
+use crate::header::SIZE_RANGE;
+use crate::header::TYPEFLAG_RANGE;
+use crate::header::field_name;
+use crate::header::vendor_issue;
+use crate::issues::Category;
+use crate::issues::Finding;
 use crate::issues::Hint;
 use crate::issues::Issue;
+use crate::issues::Severity;
+use crate::link::LinkReport;
+use crate::link::Symlink;
+use crate::link::check_hardlink;
+use crate::link::resolve_symlink;
 use crate::lint::ERROR;
-use crate::lint::lint_nul_field;
-use crate::pax::LintPaxExtendedHeader;
+use crate::listing::Visitor;
+use crate::options::Options;
+use crate::policy::compare_paths;
+use crate::policy::lint_mode;
+use crate::policy::lint_mtime;
+use crate::policy::lint_owner;
+use crate::policy::lint_reproducible;
+use crate::rule::Rule;
+use crate::rule::Violation;
+use core::cmp::Ordering;
+use core::mem;
+use core::ops::Range;
+use std::collections::BTreeMap;
 use std::collections::BTreeSet;
+use std::io::Cursor;
 use std::io::Read;
-use std::io::Result;
 
+/// Default record size of POSIX tar: 20 blocks of 512 bytes.
+pub const RECORD_SIZE: u64 = 20 * BLOCK_SIZE as u64;
+
+/// Blocking factors commonly used by tar implementations and tape drives.
+pub const BLOCKING_FACTORS: [u64; 10] = [1, 2, 4, 8, 16, 20, 32, 64, 128, 256];
+
+/// Largest device major of Linux, which uses 12 bits for it.
+pub const MAX_DEVMAJOR: u64 = 0xfff;
+
+/// Largest device minor of Linux, which uses 20 bits for it.
+pub const MAX_DEVMINOR: u64 = 0xfffff;
+
+/// Issues which are no policy violations, see EXIT STATUS in alquitran(1).
+pub const EXIT_ISSUES: i32 = 1;
+/// Processing stopped at a malformed archive or issues with severity error.
+pub const EXIT_ERRORS: i32 = 2;
+/// Only violations of policies enabled through options.
+pub const EXIT_POLICY: i32 = 3;
+/// Only hints.
+pub const EXIT_HINTS: i32 = 4;
+
+/// Header or data block with marked bytes.
+#[derive(Clone)]
 pub struct Dump {
     pub bytes: [u8; BLOCK_SIZE],
     pub marks: [u8; BLOCK_SIZE],
     pub offset: usize,
 }
 
+pub struct Records {
+    /// Total length of the archive in bytes.
+    pub length: u64,
+    /// Offset of the first byte after the end of archive marker.
+    pub end_of_archive: u64,
+    /// Amount of nul bytes directly following the end of archive marker.
+    pub padding: u64,
+}
+
+impl Records {
+    fn new(end_of_archive: u64, trailer: &[u8]) -> Records {
+        let padding = trailer.iter().take_while(|&&b| b == 0).count() as u64;
+        Records {
+            length: end_of_archive + trailer.len() as u64,
+            end_of_archive,
+            padding,
+        }
+    }
+
+    /// Default blocking factor 20 if the archive length is a multiple of
+    /// the record size, else the largest common blocking factor the
+    /// archive length is a multiple of.
+    pub fn blocking_factor(&self) -> Option<u64> {
+        let default = RECORD_SIZE / BLOCK_SIZE as u64;
+        if self.is_multiple_of(default) {
+            return Some(default);
+        }
+        BLOCKING_FACTORS
+            .iter()
+            .rev()
+            .find(|&&n| self.is_multiple_of(n))
+            .copied()
+    }
+
+    /// Returns true if archive length is a multiple of blocking factor.
+    pub fn is_multiple_of(&self, blocking_factor: u64) -> bool {
+        self.length
+            .is_multiple_of(blocking_factor * BLOCK_SIZE as u64)
+    }
+
+    /// Returns true if padding exceeds the record of end of archive marker,
+    /// using the record size of the detected blocking factor.
+    pub fn has_excess_padding(&self) -> bool {
+        let record = self.blocking_factor().unwrap_or(1) * BLOCK_SIZE as u64;
+        self.end_of_archive + self.padding > self.end_of_archive.next_multiple_of(record)
+    }
+}
+
+/// Order of entries, ignoring pax headers.
+#[derive(Default)]
+pub struct EntryOrder {
+    /// First pair of consecutive entries whose paths are not sorted, see
+    /// `compare_paths`.
+    pub unsorted: Option<(String, String)>,
+    /// First entry and the directory entry following it which contains it.
+    pub misplaced: Option<(String, String)>,
+}
+
+impl EntryOrder {
+    pub fn is_sorted(&self) -> bool {
+        self.unsorted.is_none()
+    }
+
+    pub fn has_directories_first(&self) -> bool {
+        self.misplaced.is_none()
+    }
+}
+
 pub struct ArchiveLintResult {
+    /// Archive found after end of archive marker, see `Issue::ConcatenatedArchive`.
+    pub concatenated: Option<Box<ArchiveLintResult>>,
+    /// Entries read differently by implementations.
+    pub divergences: Vec<Divergence>,
+    /// Block of the fatal issue or else the first block with findings.
     pub dump: Option<Dump>,
+    /// All blocks with findings in archive order.
+    pub dumps: Vec<Dump>,
     pub duplicated_paths: BTreeSet<String>,
+    /// Paths of processed entries in archive order, without extended
+    /// headers.
+    pub entries: Vec<String>,
+    /// Error which stopped reading a concatenated archive.
+    pub error: Option<Error>,
+    /// Issues of entries which do not stop processing.
+    pub findings: Vec<Finding>,
     pub format: Option<Format>,
     pub hints: BTreeSet<Hint>,
     pub issues: BTreeSet<Issue>,
+    pub late_directories: BTreeSet<String>,
+    pub links: Vec<LinkReport>,
+    pub order: EntryOrder,
+    pub missing_directories: BTreeSet<String>,
+    pub records: Option<Records>,
+    /// Block offset of the first header.
+    pub offset: usize,
 }
 
 impl ArchiveLintResult {
+    fn new(offset: usize) -> ArchiveLintResult {
+        ArchiveLintResult {
+            concatenated: None,
+            divergences: Vec::new(),
+            dump: None,
+            dumps: Vec::new(),
+            duplicated_paths: BTreeSet::new(),
+            entries: Vec::new(),
+            error: None,
+            findings: Vec::new(),
+            format: None,
+            hints: BTreeSet::new(),
+            issues: BTreeSet::new(),
+            late_directories: BTreeSet::new(),
+            links: Vec::new(),
+            missing_directories: BTreeSet::new(),
+            order: EntryOrder::default(),
+            records: None,
+            offset,
+        }
+    }
+
     pub fn is_portable(&self) -> bool {
         self.issues.is_empty() && self.duplicated_paths.is_empty() && self.dump.is_none()
     }
 
-    fn insert(&mut self, header: LintHeader, offset: usize) {
-        self.dump = Some(Dump {
+    /// Returns the exit status of alquitran(1) for this result, including
+    /// a concatenated archive. An issue which stops processing counts by
+    /// its severity like any other issue.
+    pub fn exit_code(&self) -> i32 {
+        let concatenated = self.concatenated.as_ref().map(|c| c.exit_code());
+        if self.error.is_some()
+            || self.issues.iter().any(|i| i.severity() == Severity::Error)
+            || self
+                .findings
+                .iter()
+                .any(|f| f.severity() == Severity::Error)
+            || concatenated == Some(EXIT_ERRORS)
+        {
+            EXIT_ERRORS
+        } else if !self.duplicated_paths.is_empty()
+            || self.issues.iter().any(|i| i.category() != Category::Policy)
+            || concatenated == Some(EXIT_ISSUES)
+        {
+            EXIT_ISSUES
+        } else if !self.is_portable() || concatenated == Some(EXIT_POLICY) {
+            EXIT_POLICY
+        } else if !self.hints.is_empty() || concatenated == Some(EXIT_HINTS) {
+            EXIT_HINTS
+        } else {
+            0
+        }
+    }
+
+    /// Adds a dump or merges its marks into the dump of the same block.
+    fn add_dump(&mut self, dump: Dump) {
+        match self.dumps.iter_mut().find(|d| d.offset == dump.offset) {
+            Some(d) => {
+                for (mark, &m) in d.marks.iter_mut().zip(dump.marks.iter()) {
+                    *mark |= m;
+                }
+            }
+            None => self.dumps.push(dump.clone()),
+        }
+        if self.dump.is_none() {
+            self.dump = Some(dump);
+        }
+    }
+
+    /// Adds dumps of the data blocks following the header at offset which
+    /// contain range of data.
+    fn add_data_dumps(&mut self, offset: usize, data: &[u8], range: Range<usize>) {
+        let first = range.start / BLOCK_SIZE;
+        let last = range.end.max(range.start + 1).div_ceil(BLOCK_SIZE);
+        for block in first..last {
+            let start = block * BLOCK_SIZE;
+            let mut dump = Dump {
+                bytes: [0; BLOCK_SIZE],
+                marks: [0; BLOCK_SIZE],
+                offset: offset + 1 + block,
+            };
+            let end = data.len().min(start + BLOCK_SIZE);
+            if start < end {
+                dump.bytes[..end - start].copy_from_slice(&data[start..end]);
+            }
+            for n in range.start.max(start)..range.end.min(start + BLOCK_SIZE) {
+                dump.marks[n - start] |= ERROR;
+            }
+            self.add_dump(dump);
+        }
+    }
+
+    /// Adds a finding concerning bytes relative to the header at offset.
+    fn add_finding(
+        &mut self,
+        issue: Issue,
+        header: Option<&LintHeader>,
+        path: &str,
+        offset: usize,
+        entry: usize,
+        range: Range<usize>,
+    ) {
+        let start = offset * BLOCK_SIZE;
+        self.issues.insert(issue);
+        self.findings.push(Finding {
+            issue,
+            path: path.to_string(),
+            offset,
+            entry,
+            location: start + range.start..start + range.end,
+            field: header.and_then(|h| field_of(&h.bytes, &range)),
+            rule: None,
+        });
+    }
+
+    /// Inserts non-fatal issues of an entry. Ranges are relative to its
+    /// header and continue into data, which is only kept for extended
+    /// headers.
+    fn insert_violations(
+        &mut self,
+        header: &LintHeader,
+        offset: usize,
+        entry: usize,
+        path: &str,
+        violations: Vec<(Issue, Range<usize>)>,
+        data: &[u8],
+    ) {
+        let mut dump = Dump {
             bytes: header.bytes,
             marks: header.marks,
             offset,
-        });
+        };
+        let mut marked = false;
+        for (_, range) in violations.iter() {
+            for n in range.start..range.end.min(BLOCK_SIZE) {
+                dump.marks[n] |= ERROR;
+                marked = true;
+            }
+        }
+        if marked {
+            self.add_dump(dump);
+        }
+        for (issue, range) in violations {
+            if range.end > BLOCK_SIZE || range.start >= BLOCK_SIZE {
+                let start = range.start.max(BLOCK_SIZE) - BLOCK_SIZE;
+                self.add_data_dumps(offset, data, start..range.end - BLOCK_SIZE);
+            }
+            self.add_finding(issue, Some(header), path, offset, entry, range);
+        }
+    }
+
+    /// Inserts violations of custom rules concerning the header at offset
+    /// and its index, or the whole archive. Archive-wide violations have
+    /// an empty path and location at the start of the archive.
+    fn insert_rule_violations(
+        &mut self,
+        entry: Option<(&LintHeader, usize, usize)>,
+        violations: Vec<Violation>,
+    ) {
+        for violation in violations {
+            let range = violation.range.clone().unwrap_or(0..0);
+            let (path, offset, index) = match entry {
+                Some((header, offset, index)) => {
+                    let path = header.path.strip_suffix('/').unwrap_or(&header.path);
+                    (path, offset, index)
+                }
+                None => ("", self.offset, 0),
+            };
+            /* rules may pass ranges beyond the header, which are not marked */
+            let marked = range.start..range.end.min(BLOCK_SIZE);
+            if let Some((header, ..)) = entry
+                && !marked.is_empty()
+            {
+                let mut dump = Dump {
+                    bytes: header.bytes,
+                    marks: header.marks,
+                    offset,
+                };
+                for n in marked {
+                    dump.marks[n] |= ERROR;
+                }
+                self.add_dump(dump);
+            }
+            let header = entry.map(|(h, ..)| h);
+            self.add_finding(Issue::RuleViolation, header, path, offset, index, range);
+            if let Some(f) = self.findings.last_mut() {
+                f.rule = Some(violation);
+            }
+        }
+    }
+
+    /// Inserts the header of a fatal issue.
+    fn insert(&mut self, header: &LintHeader, offset: usize, entry: usize) {
+        let dump = Dump {
+            bytes: header.bytes,
+            marks: header.marks,
+            offset,
+        };
+        self.add_dump(dump.clone());
+        self.dump = Some(dump);
         for &hint in header.hints.iter() {
             self.hints.insert(hint);
         }
         for &issue in header.issues.iter() {
             self.issues.insert(issue);
         }
+        let path = header.path.strip_suffix('/').unwrap_or(&header.path);
+        for (issue, range) in header.locations.iter() {
+            self.add_finding(*issue, Some(header), path, offset, entry, range.clone());
+        }
     }
 }
 
 pub struct Archive {
-    reader: Box<dyn Read>,
+    source: Source,
+    offset: usize,
+    options: Options,
+    rules: Vec<Box<dyn Rule>>,
 }
 
 impl Archive {
     pub fn new(reader: Box<dyn Read>) -> Archive {
-        Archive { reader }
+        Archive::with_options(reader, Options::default())
+    }
+
+    pub fn with_options(reader: Box<dyn Read>, options: Options) -> Archive {
+        Archive {
+            source: Source::new(reader),
+            offset: 0,
+            options,
+            rules: Vec::new(),
+        }
     }
 
+    /// Adds a custom rule which is checked while linting.
+    pub fn add_rule(&mut self, rule: Box<dyn Rule>) {
+        self.rules.push(rule);
+    }
+
+    /// Returns an iterator over the entries of the archive.
+    pub fn entries(&mut self) -> Entries<'_> {
+        Entries::new(&self.source, self.offset)
+    }
+
+    /// Lints the archive until the end of archive marker or an issue which
+    /// stops processing. Malformed headers and data are reported as issues
+    /// and findings of the result, while errors are only returned if the
+    /// archive is truncated or could not be read.
     pub fn lint(&mut self) -> Result<ArchiveLintResult> {
-        let mut result = ArchiveLintResult {
-            dump: None,
-            duplicated_paths: BTreeSet::new(),
-            format: None,
-            hints: BTreeSet::new(),
-            issues: BTreeSet::new(),
-        };
-        let mut eoa = 0;
-        let mut i = 0;
-        let mut paths = BTreeSet::new();
+        self.lint_with(None)
+    }
+
+    /// Lints the archive like `lint` while informing visitor about every
+    /// entry, including those of a concatenated archive.
+    pub fn visit(&mut self, visitor: &mut dyn Visitor) -> Result<ArchiveLintResult> {
+        self.lint_with(Some(visitor))
+    }
+
+    fn lint_with(&mut self, visitor: Option<&mut dyn Visitor>) -> Result<ArchiveLintResult> {
+        let mut result = ArchiveLintResult::new(self.offset);
+        for rule in self.rules.iter_mut() {
+            let violations = rule.start();
+            result.insert_rule_violations(None, violations);
+        }
+        self.lint_entries(&mut result, visitor)?;
+        /* records are only known if no fatal issue was found */
+        if result.records.is_some() {
+            for rule in self.rules.iter_mut() {
+                let violations = rule.end();
+                result.insert_rule_violations(None, violations);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Lints entries and trailer, returning early on fatal issues.
+    fn lint_entries(
+        &mut self,
+        result: &mut ArchiveLintResult,
+        mut visitor: Option<&mut dyn Visitor>,
+    ) -> Result<()> {
+        let mut paths = BTreeMap::new();
+        let mut names = BTreeMap::new();
+        let mut symlinks = BTreeMap::new();
+        let mut symlink_headers = BTreeMap::new();
+        let mut typeflags = BTreeMap::new();
+        let mut implicit_directories = BTreeSet::new();
+        let mut owner = None;
+        let mut mtime = None;
+        let mut previous: Option<String> = None;
         let mut read_only_directories = BTreeSet::<String>::new();
         let mut links = BTreeSet::<String>::new();
 
-        loop {
-            let mut block: [u8; BLOCK_SIZE] = [0; BLOCK_SIZE];
-            self.reader.read_exact(&mut block[..])?;
-            if lint_nul_field(&block).value.unwrap() {
-                eoa += 1;
-                if eoa == 2 {
-                    break;
+        let mut entries = Entries::new(&self.source, self.offset);
+        for (index, entry) in entries.by_ref().enumerate() {
+            let mut entry = entry?;
+            let first = result.findings.len();
+            let fatal = 'entry: {
+                let header_offset = entry.offset;
+                if let Some(d) = &entry.divergence {
+                    let header = &entry.header;
+                    let path = header.path.strip_suffix('/').unwrap_or(&header.path);
+                    let violations = vec![(Issue::ImplementationDivergence, d.range.clone())];
+                    result.insert_violations(header, header_offset, index, path, violations, &[]);
+                    result.divergences.push(d.clone());
                 }
-                i += 1;
-                continue;
-            }
-            if eoa != 0 {
-                let empty: [u8; BLOCK_SIZE] = [0; BLOCK_SIZE];
-                let header = LintHeader::new(empty);
-                result.insert(header, i - 1);
-                return Ok(result);
-            }
-            let header = LintHeader::new(block);
-            if result.format.is_none() {
-                result.format = Some(header.format);
-            } else {
-                let archive_format = result.format.unwrap();
-                if archive_format == Format::Pax {
-                    if header.format != Format::Pax && header.format != Format::Ustar {
-                        result.insert(header, i);
-                        result.issues.insert(Issue::FormatChanged);
-                        return Ok(result);
-                    }
-                    result.format = Some(Format::Pax);
-                } else if archive_format == Format::Ustar {
-                    if header.format != Format::Pax && header.format != Format::Ustar {
-                        result.insert(header, i);
-                        result.issues.insert(Issue::FormatChanged);
-                        return Ok(result);
-                    }
-                    result.format = Some(header.format);
-                } else if header.format != archive_format {
-                    result.insert(header, i);
-                    result.issues.insert(Issue::FormatChanged);
-                    return Ok(result);
+                if entry.header.checksum == Checksum::Invalid {
+                    result.insert(&entry.header, header_offset, index);
+                    break 'entry true;
+                }
+                if result.format.is_none() {
+                    result.format = Some(entry.header.format);
                 } else {
-                    result.format = Some(header.format);
+                    let archive_format = result.format.unwrap();
+                    let format = entry.header.format;
+                    if archive_format == Format::Pax {
+                        if format != Format::Pax && format != Format::Ustar {
+                            entry.header.report(Issue::FormatChanged, MAGIC_RANGE);
+                            result.insert(&entry.header, header_offset, index);
+                            break 'entry true;
+                        }
+                        result.format = Some(Format::Pax);
+                    } else if archive_format == Format::Ustar {
+                        if format != Format::Pax && format != Format::Ustar {
+                            entry.header.report(Issue::FormatChanged, MAGIC_RANGE);
+                            result.insert(&entry.header, header_offset, index);
+                            break 'entry true;
+                        }
+                        result.format = Some(format);
+                    } else if format != archive_format {
+                        entry.header.report(Issue::FormatChanged, MAGIC_RANGE);
+                        result.insert(&entry.header, header_offset, index);
+                        break 'entry true;
+                    } else {
+                        result.format = Some(format);
+                    }
                 }
-            }
-            if !header.issues.is_empty() {
-                result.insert(header, i);
-                return Ok(result);
-            } else {
-                let header_offset = i;
-                let mut data: [u8; BLOCK_SIZE] = [0; BLOCK_SIZE];
-                let count = header.get_data_block_count();
-                if count > 0 {
-                    let copy = header.typeflag == b'x' || header.typeflag == b'g';
-                    let mut xheader = Vec::new();
-                    for _b in 0..(count - 1) {
-                        self.reader.read_exact(&mut data[..])?;
-                        if copy {
-                            xheader.append(&mut data.to_vec());
+                if entry.header.is_fatal() {
+                    result.insert(&entry.header, header_offset, index);
+                    break 'entry true;
+                }
+                if let Some(p) = &entry.extended
+                    && let Some((issue, range)) = p.fatal()
+                {
+                    /* ranges of records are relative to the data block */
+                    let header = &entry.header;
+                    let path = header.path.strip_suffix('/').unwrap_or(&header.path);
+                    let range = if p.bytes.is_empty() {
+                        SIZE_RANGE
+                    } else {
+                        BLOCK_SIZE + range.start..BLOCK_SIZE + range.end
+                    };
+                    let block = header_offset + range.start / BLOCK_SIZE;
+                    let violations = vec![(*issue, range)];
+                    result.insert_violations(
+                        header,
+                        header_offset,
+                        index,
+                        path,
+                        violations,
+                        &p.bytes,
+                    );
+                    result.hints.extend(header.hints.iter());
+                    result.dump = result.dumps.iter().find(|d| d.offset == block).cloned();
+                    break 'entry true;
+                }
+                let mut head = Vec::new();
+                let metadata = matches!(entry.header.typeflag, b'g' | b'x' | b'K' | b'L' | b'X');
+                let rules = !self.rules.is_empty() && !metadata;
+                let blocks = visitor.as_deref_mut().is_some_and(|v| v.entry(&entry));
+                if !rules && !blocks {
+                    (&mut entry)
+                        .take(BLOCK_SIZE as u64)
+                        .read_to_end(&mut head)
+                        .map_err(|e| Error::at(e, self.source.position()))?;
+                } else {
+                    let mut violations = Vec::new();
+                    if rules {
+                        for rule in self.rules.iter_mut() {
+                            violations.append(&mut rule.entry(&entry.header, header_offset));
                         }
-                        i += 1;
                     }
-                    self.reader.read_exact(&mut data[..])?;
-                    let offset: usize = (header.size % BLOCK_SIZE as u64) as usize;
-                    if copy {
-                        xheader.append(&mut data[0..offset].to_vec());
-                        let pheader = LintPaxExtendedHeader::new(xheader.clone());
-                        if let Some(i) = pheader.issues.into_iter().next() {
-                            result.insert(header, header_offset);
-                            result.issues.insert(i);
-                            return Ok(result);
+                    let mut chunk = [0; 16 * BLOCK_SIZE];
+                    let mut block = entry.data_offset();
+                    loop {
+                        /* fill chunks so that only the last block is partial */
+                        let mut n = 0;
+                        while n < chunk.len() {
+                            let k = entry
+                                .read(&mut chunk[n..])
+                                .map_err(|e| Error::at(e, self.source.position()))?;
+                            if k == 0 {
+                                break;
+                            }
+                            n += k;
+                        }
+                        if n == 0 {
+                            break;
+                        }
+                        if head.len() < BLOCK_SIZE {
+                            let k = (BLOCK_SIZE - head.len()).min(n);
+                            head.extend_from_slice(&chunk[..k]);
+                        }
+                        if rules {
+                            for rule in self.rules.iter_mut() {
+                                violations.append(&mut rule.data(&entry.header, &chunk[..n]));
+                            }
+                        }
+                        if blocks && let Some(v) = visitor.as_deref_mut() {
+                            for bytes in chunk[..n].chunks_exact(BLOCK_SIZE) {
+                                v.block(block, bytes.try_into().unwrap());
+                                block += 1;
+                            }
+                        }
+                        if n < chunk.len() {
+                            break;
                         }
                     }
-                    i += 1;
-                    if offset != 0 && data[offset..BLOCK_SIZE].iter().any(|&x| x != 0) {
+                    result.insert_rule_violations(
+                        Some((&entry.header, header_offset, index)),
+                        violations,
+                    );
+                }
+                if let Some((offset, data)) = entry.last_block()? {
+                    let used = (entry.len() % BLOCK_SIZE as u64) as usize;
+                    /* the partial last block is passed along with its padding */
+                    if blocks
+                        && used != 0
+                        && let Some(v) = visitor.as_deref_mut()
+                    {
+                        v.block(offset, &data);
+                    }
+                    if used != 0 && data[used..BLOCK_SIZE].iter().any(|&x| x != 0) {
                         let mut dump = Dump {
                             bytes: data,
                             marks: [0; BLOCK_SIZE],
-                            offset: i,
+                            offset,
                         };
-                        for n in offset..BLOCK_SIZE {
+                        for n in used..BLOCK_SIZE {
                             if dump.bytes[n] != 0 {
                                 dump.marks[n] |= ERROR;
                             }
                         }
+                        result.add_dump(dump.clone());
                         result.dump = Some(dump);
+                        let path = entry
+                            .header
+                            .path
+                            .strip_suffix('/')
+                            .unwrap_or(&entry.header.path);
                         result.issues.insert(Issue::DataPaddingNotNul);
-                        return Ok(result);
+                        result.findings.push(Finding {
+                            issue: Issue::DataPaddingNotNul,
+                            path: path.to_string(),
+                            offset: header_offset,
+                            entry: index,
+                            location: offset * BLOCK_SIZE + used..(offset + 1) * BLOCK_SIZE,
+                            field: None,
+                            rule: None,
+                        });
+                        break 'entry true;
                     }
                 }
-            }
-            let mut path = header.path.clone();
-            if path.ends_with('/') {
-                path.pop();
-            }
-            for dir in read_only_directories.iter() {
-                if path.starts_with(dir.as_str()) {
-                    result.insert(header, i);
-                    result.issues.insert(Issue::ReadOnlyDirectoryWithEntries);
-                    return Ok(result);
+                let header = &mut entry.header;
+                let mut path = header.path.clone();
+                if path.ends_with('/') {
+                    path.pop();
                 }
-            }
-            for link in links.iter() {
-                if path.starts_with(link.as_str()) {
-                    result.insert(header, i);
-                    result.issues.insert(Issue::LinkIsParent);
-                    return Ok(result);
+                /* issues of the header which do not stop processing */
+                let mut violations = header.locations.clone();
+                if let Some(issue) = vendor_issue(header.typeflag) {
+                    violations.push((issue, TYPEFLAG_RANGE));
                 }
+                if let Some(p) = &entry.extended {
+                    for (issue, range) in p.locations.iter() {
+                        violations.push((*issue, BLOCK_SIZE + range.start..BLOCK_SIZE + range.end));
+                    }
+                }
+                /*
+                 * GNU long names and long link names apply to the next
+                 * header and are no entries on their own.
+                 */
+                if header.typeflag == b'K' || header.typeflag == b'L' {
+                    result.insert_violations(header, header_offset, index, &path, violations, &[]);
+                    break 'entry false;
+                }
+                /* GNU dump directories are extracted as directories */
+                let typeflag = if header.typeflag == b'D' {
+                    b'5'
+                } else {
+                    header.typeflag
+                };
+                for dir in read_only_directories.iter() {
+                    if path.starts_with(dir.as_str()) {
+                        header.report(Issue::ReadOnlyDirectoryWithEntries, NAME_RANGE);
+                        result.insert(header, header_offset, index);
+                        break 'entry true;
+                    }
+                }
+                for link in links.iter() {
+                    if path.starts_with(link.as_str()) {
+                        header.report(Issue::LinkIsParent, NAME_RANGE);
+                        result.insert(header, header_offset, index);
+                        break 'entry true;
+                    }
+                }
+                if header.typeflag == b'5' && (header.mode & 0o200) == 0 {
+                    let mut dir = path.clone();
+                    dir.push('/');
+                    read_only_directories.insert(dir);
+                }
+                if header.typeflag == b'1' || header.typeflag == b'2' {
+                    let mut dir = path.clone();
+                    dir.push('/');
+                    links.insert(dir);
+                }
+                let extended = header.typeflag == b'g' || header.typeflag == b'x';
+                if !extended {
+                    result.entries.push(path.clone());
+                }
+                if !extended && let Some(issue) = lint_conflict(&path, typeflag, &typeflags) {
+                    header.report(issue, NAME_RANGE);
+                    result.insert(header, header_offset, index);
+                    break 'entry true;
+                }
+                if paths.contains_key(&path) {
+                    header.report(Issue::DuplicatePath, NAME_RANGE);
+                    result.insert(header, header_offset, index);
+                    result.duplicated_paths.insert(path);
+                    break 'entry true;
+                }
+                if header.typeflag == b'1'
+                    && let Some(r) = check_hardlink(
+                        &path,
+                        header_offset,
+                        &header.raw_linkname,
+                        &paths,
+                        &names,
+                        &typeflags,
+                    )
+                {
+                    /* the target exists and only its spelling differs */
+                    if r.issue == Issue::HardLinkNotNormalized {
+                        violations.push((r.issue, LINKNAME_RANGE));
+                        result.links.push(r);
+                    } else {
+                        header.report(r.issue, LINKNAME_RANGE);
+                        result.insert(header, header_offset, index);
+                        result.links.push(r);
+                        break 'entry true;
+                    }
+                }
+                if header.typeflag == b'2' {
+                    let symlink = Symlink {
+                        linkname: header.linkname.clone(),
+                        offset: header_offset,
+                    };
+                    symlinks.insert(path.clone(), symlink);
+                    symlink_headers.insert(path.clone(), (header.clone(), index));
+                }
+                if self.options.parent_directories
+                    && header.typeflag != b'g'
+                    && header.typeflag != b'x'
+                {
+                    if header.typeflag == b'5' && implicit_directories.remove(&path) {
+                        result.late_directories.insert(path.clone());
+                    }
+                    for (n, _) in path.match_indices('/') {
+                        if typeflags.get(&path[..n]) != Some(&b'5') {
+                            implicit_directories.insert(path[..n].to_string());
+                        }
+                    }
+                }
+                violations.append(&mut lint_mode(header, &head, &self.options));
+                violations.append(&mut lint_owner(header, &self.options, &mut owner));
+                violations.append(&mut lint_mtime(header, &self.options));
+                let extended_header = entry.extended.as_ref();
+                violations.append(&mut lint_reproducible(
+                    header,
+                    extended_header,
+                    &self.options,
+                    &mut mtime,
+                ));
+                if header.typeflag != b'g' && header.typeflag != b'x' {
+                    if let Some(p) = previous
+                        && compare_paths(&p, &path) == Ordering::Greater
+                    {
+                        if self.options.sorted_entries {
+                            violations.push((Issue::UnsortedEntries, NAME_RANGE));
+                        }
+                        if result.order.unsorted.is_none() {
+                            result.order.unsorted = Some((p, path.clone()));
+                        }
+                    }
+                    if header.typeflag == b'5' && result.order.misplaced.is_none() {
+                        let dir = format!("{}/", path);
+                        result.order.misplaced = paths
+                            .range(dir.clone()..)
+                            .take_while(|(p, _)| p.starts_with(&dir))
+                            .find(|(p, _)| typeflags.contains_key(*p))
+                            .map(|(p, _)| (p.clone(), path.clone()));
+                    }
+                    previous = Some(path.clone());
+                }
+                if header.mtime_nanos != 0 {
+                    result.hints.insert(Hint::FractionalTimestamp);
+                }
+                /*
+                 * Special files cannot be created by unprivileged users and
+                 * have no use in source archives.
+                 */
+                let special = matches!(header.typeflag, b'3' | b'4' | b'6');
+                if self.options.special_files && special {
+                    violations.push((Issue::SpecialFile, TYPEFLAG_RANGE));
+                }
+                if header.devmajor > MAX_DEVMAJOR {
+                    violations.push((Issue::LargeDeviceNumber, DEVMAJOR_RANGE));
+                }
+                if header.devminor > MAX_DEVMINOR {
+                    violations.push((Issue::LargeDeviceNumber, DEVMINOR_RANGE));
+                }
+                let data = extended_header.map_or(&[][..], |p| &p.bytes);
+                result.insert_violations(header, header_offset, index, &path, violations, data);
+                /* extended headers are not extracted and cannot conflict */
+                if !extended {
+                    typeflags.insert(path.clone(), typeflag);
+                }
+                names.insert(path.clone(), header.raw_path.clone());
+                paths.insert(path, header_offset);
+                false
+            };
+            if let Some(v) = visitor.as_deref_mut() {
+                v.checked(&entry, &result.findings[first..]);
             }
-            if header.typeflag == b'5' && (header.mode & 0o200) == 0 {
-                let mut dir = path.clone();
-                dir.push('/');
-                read_only_directories.insert(dir);
-            }
-            if header.typeflag == b'1' || header.typeflag == b'2' {
-                let mut dir = path.clone();
-                dir.push('/');
-                links.insert(dir);
+            if fatal {
+                return Ok(());
             }
-            if paths.contains(&path) {
-                result.insert(header, i);
-                result.duplicated_paths.insert(path);
-                return Ok(result);
-            } else {
-                paths.insert(path);
+        }
+        if !result.late_directories.is_empty() {
+            result.issues.insert(Issue::LateParentDirectory);
+        }
+        if !implicit_directories.is_empty() {
+            result.issues.insert(Issue::MissingParentDirectory);
+            result.missing_directories = implicit_directories;
+        }
+        let first = result.findings.len();
+        let mut ordered: Vec<_> = symlink_headers.iter().collect();
+        ordered.sort_by_key(|(_, (_, index))| *index);
+        for (path, (header, index)) in ordered {
+            if let Some(r) = resolve_symlink(path, &symlinks, &paths) {
+                let violations = vec![(r.issue, LINKNAME_RANGE)];
+                let offset = symlinks[path].offset;
+                result.insert_violations(header, offset, *index, path, violations, &[]);
+                result.links.push(r);
             }
-            i += 1;
+        }
+        if let Some(v) = visitor.as_deref_mut() {
+            v.resolved(&result.findings[first..]);
         }
         let mut eof = Vec::new();
-        self.reader.read_to_end(&mut eof)?;
-        if eof.iter().any(|&b| b != 0) {
+        self.source.read_to_end(&mut eof)?;
+        let i = entries.offset();
+        if let Some(start) = find_archive(&eof) {
+            /*
+             * GNU tar with --ignore-zeros reads past the end of archive
+             * marker and extracts the entries of the concatenated
+             * archive, while other implementations stop. Lint the
+             * hidden archive as well so that its entries are known,
+             * with the same rules.
+             */
+            result.issues.insert(Issue::ConcatenatedArchive);
+            let mut embedded = Archive {
+                source: Source::new(Box::new(Cursor::new(eof[start..].to_vec()))),
+                offset: i + start / BLOCK_SIZE,
+                options: self.options.clone(),
+                rules: mem::take(&mut self.rules),
+            };
+            let mut concatenated = ArchiveLintResult::new(embedded.offset);
+            if let Err(e) = embedded.lint_entries(&mut concatenated, visitor) {
+                concatenated.error = Some(e);
+            }
+            self.rules = embedded.rules;
+            result.concatenated = Some(Box::new(concatenated));
+        } else if eof.iter().any(|&b| b != 0) {
             result.issues.insert(Issue::TrailingByteNotNul);
         }
-        Ok(result)
+        let records = Records::new(((i - self.offset) * BLOCK_SIZE) as u64, &eof);
+        if !records.is_multiple_of(1) {
+            result.issues.insert(Issue::PartialBlock);
+        } else if !records.is_multiple_of(RECORD_SIZE / BLOCK_SIZE as u64) {
+            result.hints.insert(Hint::PartialRecord);
+        }
+        if records.has_excess_padding() {
+            result.hints.insert(Hint::ExcessPadding);
+        }
+        result.records = Some(records);
+        Ok(())
+    }
+}
+
+/// Returns the name of the header field of block in which range starts,
+/// None if it is empty or lies outside of the header.
+fn field_of(block: &[u8; BLOCK_SIZE], range: &Range<usize>) -> Option<&'static str> {
+    if range.is_empty() {
+        return None;
+    }
+    field_name(block, range.start)
+}
+
+/// Checks if entry and previously seen entries disagree about a path being
+/// a directory.
+fn lint_conflict(path: &str, typeflag: u8, typeflags: &BTreeMap<String, u8>) -> Option<Issue> {
+    for (n, _) in path.match_indices('/') {
+        if typeflags.get(&path[..n]).is_some_and(|&t| t != b'5') {
+            return Some(Issue::ParentNotDirectory);
+        }
+    }
+    let previous = typeflags.get(path).copied();
+    if typeflag == b'5' {
+        if previous.is_some_and(|t| t != b'5') {
+            return Some(Issue::DirectoryReplacesFile);
+        }
+    } else {
+        let dir = format!("{}/", path);
+        let parent = typeflags
+            .range(dir.clone()..)
+            .next()
+            .is_some_and(|(p, _)| p.starts_with(&dir));
+        if parent || previous == Some(b'5') {
+            return Some(Issue::FileReplacesDirectory);
+        }
+    }
+    None
+}
+
+/// Finds the first block after nul blocks that is a valid tar header.
+fn find_archive(bytes: &[u8]) -> Option<usize> {
+    let block = bytes
+        .chunks_exact(BLOCK_SIZE)
+        .position(|b| b.iter().any(|&x| x != 0))?;
+    let start = block * BLOCK_SIZE;
+    let header = LintHeader::new(bytes[start..start + BLOCK_SIZE].try_into().unwrap());
+    if header.issues.contains(&Issue::InvalidChecksum) {
+        None
+    } else {
+        Some(start)
     }
 }
@@ -0,0 +1,257 @@
+use crate::header::BLOCK_SIZE;
+use crate::header::CKSUM_RANGE;
+use crate::header::MAGIC_RANGE;
+use crate::header::NAME_RANGE;
+use crate::header::PREFIX_RANGE;
+use crate::header::SIZE_RANGE;
+use crate::header::TYPEFLAG_RANGE;
+use crate::header::VENDOR_TYPEFLAGS;
+use crate::header::VERSION_RANGE;
+use crate::header::calculate_checksum;
+use crate::header::calculate_signed_checksum;
+use core::ops::Range;
+use std::collections::BTreeMap;
+
+/// Tar implementations whose reading behaviour is modelled.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Implementation {
+    Bsdtar,
+    Busybox,
+    GnuTar,
+    Go,
+    Python,
+}
+
+impl Implementation {
+    pub const ALL: [Implementation; 5] = [
+        Implementation::Bsdtar,
+        Implementation::Busybox,
+        Implementation::GnuTar,
+        Implementation::Go,
+        Implementation::Python,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Implementation::Bsdtar => "bsdtar",
+            Implementation::Busybox => "busybox",
+            Implementation::GnuTar => "GNU tar",
+            Implementation::Go => "Go archive/tar",
+            Implementation::Python => "Python tarfile",
+        }
+    }
+
+    /// Checks if header with given magic and version is read at all.
+    ///
+    /// Busybox stops at any magic which is neither ustar nor empty, all
+    /// others fall back to v7 headers.
+    fn accepts_magic(&self, magic: &[u8]) -> bool {
+        match self {
+            Implementation::Busybox => magic.starts_with(b"ustar") || magic[..5] == [0; 5],
+            _ => true,
+        }
+    }
+
+    /// Checks if the prefix field is prepended to the name field.
+    ///
+    /// GNU headers store times in the prefix field. Go requires the
+    /// ustar version as well, while Python only ignores prefixes of GNU
+    /// extension types.
+    fn uses_prefix(&self, magic: &[u8], version: &[u8], typeflag: u8) -> bool {
+        let gnu = magic == b"ustar " && version == b" \0";
+        let ustar = magic == b"ustar\0";
+        match self {
+            Implementation::Bsdtar | Implementation::GnuTar => ustar,
+            Implementation::Busybox => !gnu,
+            Implementation::Go => ustar && version == b"00",
+            Implementation::Python => !b"KLS".contains(&typeflag),
+        }
+    }
+
+    /// Checks if a pax extended header keyword is supported.
+    ///
+    /// Busybox only supports path and linkpath.
+    fn supports_pax(&self, keyword: &str) -> bool {
+        match self {
+            Implementation::Busybox => keyword == "path" || keyword == "linkpath",
+            _ => true,
+        }
+    }
+
+    /// Returns the effective typeflag of an entry or None if reading
+    /// stops at this entry.
+    fn typeflag(&self, typeflag: u8, path: &str) -> Option<u8> {
+        /*
+         * Regular files with a trailing slash are directories in
+         * old archives. Go and Python only honour this for the v7
+         * typeflag NUL.
+         */
+        if path.ends_with('/') && (typeflag == 0 || typeflag == b'0' || typeflag == b'7') {
+            let directory = match self {
+                Implementation::GnuTar => true,
+                Implementation::Bsdtar | Implementation::Busybox => typeflag != b'7',
+                Implementation::Go | Implementation::Python => typeflag == 0,
+            };
+            if directory {
+                return Some(b'5');
+            }
+        }
+        if typeflag == 0 || typeflag == b'7' {
+            return Some(b'0');
+        }
+        if (b'0'..=b'7').contains(&typeflag) || VENDOR_TYPEFLAGS.contains(&typeflag) {
+            return Some(typeflag);
+        }
+        /*
+         * POSIX requires unknown types to be extracted as regular files.
+         * Busybox aborts and Go passes them on unchanged.
+         */
+        match self {
+            Implementation::Busybox => None,
+            Implementation::Go => Some(typeflag),
+            _ => Some(b'0'),
+        }
+    }
+}
+
+/// Entry as seen by an implementation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Interpretation {
+    pub path: String,
+    pub typeflag: u8,
+    pub size: u64,
+}
+
+/// Entry which is read differently by implementations.
+#[derive(Clone)]
+pub struct Divergence {
+    /// Block offset of the header.
+    pub offset: usize,
+    /// Range of the header field the implementations disagree about.
+    pub range: Range<usize>,
+    /// Interpretation of every implementation, None if it stops reading.
+    pub interpretations: Vec<(Implementation, Option<Interpretation>)>,
+}
+
+/// Interprets header as given implementation would, taking records of a
+/// preceding pax extended header and a GNU long name into account.
+pub fn interpret(
+    implementation: Implementation,
+    bytes: &[u8; BLOCK_SIZE],
+    records: &BTreeMap<String, Vec<u8>>,
+    long_name: Option<&str>,
+) -> Option<Interpretation> {
+    let magic = &bytes[MAGIC_RANGE];
+    if !implementation.accepts_magic(magic) {
+        return None;
+    }
+    /*
+     * All modelled implementations accept checksums calculated with
+     * signed bytes, as written by historic Sun tar, as well.
+     */
+    let unsigned = calculate_checksum(bytes);
+    let signed = calculate_signed_checksum(bytes);
+    let checksum = octal(&bytes[CKSUM_RANGE])?;
+    if checksum != unsigned && checksum as i64 != signed {
+        return None;
+    }
+    let typeflag = bytes[TYPEFLAG_RANGE.start];
+    let mut path = string(&bytes[NAME_RANGE]);
+    let prefix = string(&bytes[PREFIX_RANGE]);
+    if !prefix.is_empty() && implementation.uses_prefix(magic, &bytes[VERSION_RANGE], typeflag) {
+        path = prefix + "/" + &path;
+    }
+    if let Some(name) = long_name {
+        path = name.to_string();
+    }
+    let mut size = number(&bytes[SIZE_RANGE])?;
+    for (keyword, value) in records.iter() {
+        if !implementation.supports_pax(keyword) {
+            continue;
+        }
+        let value = String::from_utf8_lossy(value).to_string();
+        match keyword.as_str() {
+            "path" => path = value,
+            "size" => size = value.parse().ok()?,
+            _ => {}
+        }
+    }
+    let typeflag = implementation.typeflag(typeflag, &path)?;
+    while path.len() > 1 && path.ends_with('/') {
+        path.pop();
+    }
+    Some(Interpretation {
+        path,
+        typeflag,
+        size,
+    })
+}
+
+/// Returns interpretations of all implementations if they disagree.
+pub fn diverge(
+    bytes: &[u8; BLOCK_SIZE],
+    records: &BTreeMap<String, Vec<u8>>,
+    long_name: Option<&str>,
+    offset: usize,
+) -> Option<Divergence> {
+    let interpretations: Vec<_> = Implementation::ALL
+        .iter()
+        .map(|&i| (i, interpret(i, bytes, records, long_name)))
+        .collect();
+    let first = &interpretations[0].1;
+    if interpretations.iter().all(|(_, i)| i == first) {
+        return None;
+    }
+    let read: Vec<&Interpretation> = interpretations
+        .iter()
+        .filter_map(|(_, i)| i.as_ref())
+        .collect();
+    let differ = |f: fn(&Interpretation) -> u64| read.iter().any(|i| f(i) != f(read[0]));
+    let range = if read.iter().any(|i| i.path != read[0].path) {
+        NAME_RANGE
+    } else if differ(|i| i.typeflag as u64) {
+        TYPEFLAG_RANGE
+    } else if differ(|i| i.size) {
+        SIZE_RANGE
+    } else if Implementation::ALL
+        .iter()
+        .any(|i| !i.accepts_magic(&bytes[MAGIC_RANGE]))
+    {
+        MAGIC_RANGE
+    } else {
+        TYPEFLAG_RANGE
+    };
+    Some(Divergence {
+        offset,
+        range,
+        interpretations,
+    })
+}
+
+/// Parses leniently like most implementations: leading spaces and
+/// trailing spaces or nuls are ignored.
+fn octal(field: &[u8]) -> Option<u64> {
+    let digits = String::from_utf8_lossy(field);
+    let digits = digits.trim_matches(|c| c == ' ' || c == '\0');
+    u64::from_str_radix(digits, 8).ok()
+}
+
+/// Parses octal or base-256 number.
+fn number(field: &[u8]) -> Option<u64> {
+    if field[0] & 0x80 != 0 {
+        let mut value: u64 = (field[0] & 0x7f) as u64;
+        for &b in field[1..].iter() {
+            value = value.checked_mul(256)? + b as u64;
+        }
+        return Some(value);
+    }
+    if field.iter().all(|&b| b == 0) {
+        return Some(0);
+    }
+    octal(field)
+}
+
+fn string(field: &[u8]) -> String {
+    let end = field.iter().position(|&b| b == 0).unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).to_string()
+}
@@ -0,0 +1,377 @@
+use crate::divergence::Divergence;
+use crate::divergence::diverge;
+use crate::error::Error;
+use crate::error::Result;
+use crate::header::BLOCK_SIZE;
+use crate::header::Format;
+use crate::header::GNU_SPARSE_EXTENDED;
+use crate::header::GNU_SPARSE_EXTENSION_EXTENDED;
+use crate::header::LintHeader;
+use crate::issues::Issue;
+use crate::lint::lint_nul_field;
+use crate::pax::LintPaxExtendedHeader;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::io;
+use std::io::Read;
+
+/// Reader of an archive shared by its entries.
+pub struct Source {
+    reader: RefCell<Box<dyn Read>>,
+    /// Amount of bytes read so far.
+    position: Cell<u64>,
+}
+
+impl Source {
+    pub fn new(reader: Box<dyn Read>) -> Source {
+        Source {
+            reader: RefCell::new(reader),
+            position: Cell::new(0),
+        }
+    }
+
+    fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.reader.borrow_mut().read(buf)?;
+        self.position.set(self.position.get() + n as u64);
+        Ok(n)
+    }
+
+    fn read_exact(&self, buf: &mut [u8]) -> Result<()> {
+        self.reader
+            .borrow_mut()
+            .read_exact(buf)
+            .map_err(|e| Error::at(e, self.position.get()))?;
+        self.position.set(self.position.get() + buf.len() as u64);
+        Ok(())
+    }
+
+    /// Returns the amount of bytes read so far.
+    pub fn position(&self) -> u64 {
+        self.position.get()
+    }
+
+    /// Reads all remaining bytes.
+    pub fn read_to_end(&self, buf: &mut Vec<u8>) -> Result<usize> {
+        let n = self
+            .reader
+            .borrow_mut()
+            .read_to_end(buf)
+            .map_err(|e| Error::at(e, self.position.get()))?;
+        self.position.set(self.position.get() + n as u64);
+        Ok(n)
+    }
+
+    fn skip(&self, amount: u64) -> Result<()> {
+        let mut reader = self.reader.borrow_mut();
+        let n = io::copy(&mut reader.by_ref().take(amount), &mut io::sink())
+            .map_err(|e| Error::at(e, self.position.get()))?;
+        self.position.set(self.position.get() + n);
+        if n != amount {
+            return Err(Error::Truncated {
+                offset: self.position.get(),
+            });
+        }
+        Ok(())
+    }
+}
+
+enum Data {
+    /// Data of extended headers which is read while iterating.
+    Buffered(Vec<u8>),
+    /// Data which is read from source on demand.
+    Stream,
+}
+
+/// Entry of an archive, i.e. a header and its data.
+///
+/// Extended headers like pax and GNU long names are returned as entries
+/// as well, while their content is applied to the header of the next
+/// entry.
+pub struct Entry<'a> {
+    pub header: LintHeader,
+    /// Block offset of the header.
+    pub offset: usize,
+    /// Parsed records if this is a pax or Solaris extended header.
+    pub extended: Option<LintPaxExtendedHeader>,
+    /// Differences between implementations reading this entry.
+    pub divergence: Option<Divergence>,
+    source: &'a Source,
+    data: Data,
+    /// Block offset of the first data block.
+    data_offset: usize,
+    /// Position of first data byte in source.
+    start: u64,
+    length: u64,
+    consumed: u64,
+    /// Last data block, filled while data is consumed.
+    tail: [u8; BLOCK_SIZE],
+}
+
+impl Entry<'_> {
+    /// Returns issues found while reading header and extended header.
+    /// Findings of policy checks and checks across entries are passed to
+    /// a `Visitor` while linting.
+    pub fn issues(&self) -> BTreeSet<Issue> {
+        let mut issues = self.header.issues.clone();
+        if let Some(p) = &self.extended {
+            issues.extend(p.issues.iter().copied());
+        }
+        if self.divergence.is_some() {
+            issues.insert(Issue::ImplementationDivergence);
+        }
+        issues
+    }
+
+    /// Returns the size of data without padding.
+    pub fn len(&self) -> u64 {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns the block offset of the first data block.
+    pub fn data_offset(&self) -> usize {
+        self.data_offset
+    }
+
+    /// Skips remaining data and returns the block offset and bytes of the
+    /// last data block including its padding, if there is data at all.
+    pub fn last_block(&mut self) -> Result<Option<(usize, [u8; BLOCK_SIZE])>> {
+        if self.length == 0 {
+            return Ok(None);
+        }
+        let tail = self.tail_start();
+        if self.consumed < tail {
+            if let Data::Stream = self.data {
+                let position = self.start + self.consumed;
+                if self.source.position.get() != position {
+                    return Err(self.moved());
+                }
+                self.source.skip(tail - self.consumed)?;
+            }
+            self.consumed = tail;
+        }
+        let mut buf = [0; BLOCK_SIZE];
+        while self
+            .read(&mut buf)
+            .map_err(|e| Error::at(e, self.source.position.get()))?
+            != 0
+        {}
+        let used = (self.length - tail) as usize;
+        let padding = self.start + self.length;
+        if self.source.position.get() == padding {
+            self.source.read_exact(&mut self.tail[used..])?;
+        } else if self.source.position.get() != padding + (BLOCK_SIZE - used) as u64 {
+            return Err(self.moved());
+        }
+        let blocks = self.length.div_ceil(BLOCK_SIZE as u64) as usize;
+        Ok(Some((self.data_offset + blocks - 1, self.tail)))
+    }
+
+    fn moved(&self) -> Error {
+        Error::Moved {
+            offset: self.source.position.get(),
+        }
+    }
+
+    fn tail_start(&self) -> u64 {
+        (self.length - 1) / BLOCK_SIZE as u64 * BLOCK_SIZE as u64
+    }
+}
+
+impl Read for Entry<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.length - self.consumed;
+        let amount = buf.len().min(remaining as usize);
+        if amount == 0 {
+            return Ok(0);
+        }
+        let from = self.consumed as usize;
+        let n = match &self.data {
+            Data::Buffered(data) => {
+                buf[..amount].copy_from_slice(&data[from..from + amount]);
+                amount
+            }
+            Data::Stream => {
+                if self.source.position.get() != self.start + self.consumed {
+                    return Err(io::Error::other(self.moved()));
+                }
+                self.source.read(&mut buf[..amount])?
+            }
+        };
+        let tail = self.tail_start() as usize;
+        let end = from + n;
+        if end > tail {
+            let begin = from.max(tail);
+            self.tail[begin - tail..end - tail].copy_from_slice(&buf[begin - from..n]);
+        }
+        self.consumed += n as u64;
+        Ok(n)
+    }
+}
+
+/// Iterator over entries of an archive.
+///
+/// Iteration stops at the end of archive marker or after an entry whose
+/// header has fatal issues, because the location of the next header is
+/// unknown then.
+pub struct Entries<'a> {
+    source: &'a Source,
+    /// Block offset of the archive start.
+    start: usize,
+    /// Block offset of the next header.
+    next: usize,
+    done: bool,
+    extended: Option<BTreeMap<String, Vec<u8>>>,
+    long_name: Option<String>,
+    long_link: Option<String>,
+}
+
+impl<'a> Entries<'a> {
+    pub fn new(source: &'a Source, start: usize) -> Entries<'a> {
+        Entries {
+            source,
+            start,
+            next: start,
+            done: false,
+            extended: None,
+            long_name: None,
+            long_link: None,
+        }
+    }
+
+    /// Returns the block offset of the next header or after the end of
+    /// archive marker once iteration finished.
+    pub fn offset(&self) -> usize {
+        self.next
+    }
+
+    fn read_entry(&mut self) -> Result<Option<Entry<'a>>> {
+        let target = ((self.next - self.start) * BLOCK_SIZE) as u64;
+        let position = self.source.position.get();
+        if position > target {
+            return Err(Error::Moved { offset: position });
+        }
+        self.source.skip(target - position)?;
+        let mut block: [u8; BLOCK_SIZE] = [0; BLOCK_SIZE];
+        let mut eoa = 0;
+        loop {
+            self.source.read_exact(&mut block[..])?;
+            self.next += 1;
+            if !lint_nul_field(&block).value.unwrap() {
+                break;
+            }
+            eoa += 1;
+            if eoa == 2 {
+                self.done = true;
+                return Ok(None);
+            }
+        }
+        if eoa != 0 {
+            self.done = true;
+            let empty: [u8; BLOCK_SIZE] = [0; BLOCK_SIZE];
+            return Ok(Some(self.entry(LintHeader::new(empty), self.next - 2)));
+        }
+        let offset = self.next - 1;
+        let mut header = LintHeader::new(block);
+        let records = self.extended.take().unwrap_or_default();
+        header.apply_pax(&records);
+        let name = self.long_name.take();
+        let divergence = if matches!(header.typeflag, b'g' | b'x' | b'K' | b'L') {
+            None
+        } else {
+            diverge(&block, &records, name.as_deref(), offset)
+        };
+        header.apply_long_names(name, self.long_link.take());
+        if header.is_fatal() {
+            self.done = true;
+            let mut entry = self.entry(header, offset);
+            entry.divergence = divergence;
+            return Ok(Some(entry));
+        }
+        /*
+         * Old GNU sparse headers continue their sparse map in
+         * extension blocks which precede the data.
+         */
+        if header.format == Format::Gnu
+            && header.typeflag == b'S'
+            && block[GNU_SPARSE_EXTENDED] != 0
+        {
+            loop {
+                self.source.read_exact(&mut block[..])?;
+                self.next += 1;
+                if block[GNU_SPARSE_EXTENSION_EXTENDED] == 0 {
+                    break;
+                }
+            }
+        }
+        let count = header.get_data_block_count();
+        let mut entry = self.entry(header, offset);
+        entry.divergence = divergence;
+        if count > 0 {
+            entry.length = entry.header.size;
+        }
+        self.next += count as usize;
+        let typeflag = entry.header.typeflag;
+        if matches!(typeflag, b'g' | b'x' | b'K' | b'L' | b'X') {
+            let mut data = vec![0; entry.length as usize];
+            self.source.read_exact(&mut data)?;
+            if typeflag == b'K' || typeflag == b'L' {
+                let end = data.iter().position(|&b| b == 0).unwrap_or(data.len());
+                let name = String::from_utf8_lossy(&data[..end]).into_owned();
+                if typeflag == b'L' {
+                    self.long_name = Some(name);
+                } else {
+                    self.long_link = Some(name);
+                }
+            } else {
+                let pheader = LintPaxExtendedHeader::new(data.clone());
+                if pheader.fatal().is_none() {
+                    self.extended = Some(pheader.records.clone());
+                } else {
+                    self.done = true;
+                }
+                entry.extended = Some(pheader);
+            }
+            entry.data = Data::Buffered(data);
+        }
+        Ok(Some(entry))
+    }
+
+    fn entry(&self, header: LintHeader, offset: usize) -> Entry<'a> {
+        Entry {
+            header,
+            offset,
+            extended: None,
+            divergence: None,
+            source: self.source,
+            data: Data::Stream,
+            data_offset: self.next,
+            start: self.source.position.get(),
+            length: 0,
+            consumed: 0,
+            tail: [0; BLOCK_SIZE],
+        }
+    }
+}
+
+impl<'a> Iterator for Entries<'a> {
+    type Item = Result<Entry<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.read_entry() {
+            Ok(entry) => entry.map(Ok),
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use std::error;
+use std::fmt;
+use std::io;
+use std::io::ErrorKind;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Error which prevents reading an archive.
+///
+/// Offsets are byte offsets in the read stream at which the failing
+/// read started. Malformed headers are no errors, but reported as issues
+/// of `ArchiveLintResult`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Archive ends within a header or data of an entry.
+    Truncated { offset: u64 },
+    /// Entry data was read after iteration moved past it.
+    Moved { offset: u64 },
+    /// Reading from underlying reader failed.
+    Io { offset: u64, source: io::Error },
+}
+
+impl Error {
+    /// Converts an I/O error, recovering errors which were passed through
+    /// `Read` of an entry.
+    pub(crate) fn at(source: io::Error, offset: u64) -> Error {
+        if source.get_ref().is_some_and(|e| e.is::<Error>()) {
+            return *source.into_inner().unwrap().downcast::<Error>().unwrap();
+        }
+        if source.kind() == ErrorKind::UnexpectedEof {
+            Error::Truncated { offset }
+        } else {
+            Error::Io { offset, source }
+        }
+    }
+
+    /// Returns the byte offset at which the error occurred.
+    pub fn offset(&self) -> u64 {
+        match self {
+            Error::Truncated { offset } | Error::Moved { offset } | Error::Io { offset, .. } => {
+                *offset
+            }
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Truncated { offset } => write!(f, "archive truncated at offset {:08x}", offset),
+            Error::Moved { offset } => {
+                write!(f, "archive moved past entry data at offset {:08x}", offset)
+            }
+            Error::Io { offset, .. } => write!(f, "read error at offset {:08x}", offset),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
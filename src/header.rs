@@ -8,6 +8,7 @@ use crate::lint::lint_number_field;
 use crate::lint::lint_path_field;
 use crate::lint::lint_string_field;
 use core::ops::Range;
+use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 
 pub const BLOCK_SIZE: usize = 512;
@@ -30,6 +31,108 @@ pub const DEVMINOR_RANGE: Range<usize> = 337..345;
 pub const PREFIX_RANGE: Range<usize> = 345..500;
 pub const USTAR_PADDING_RANGE: Range<usize> = 500..BLOCK_SIZE;
 
+/// Names of ustar header fields.
+pub const FIELDS: [(&str, Range<usize>); 17] = [
+    ("name", NAME_RANGE),
+    ("mode", MODE_RANGE),
+    ("uid", UID_RANGE),
+    ("gid", GID_RANGE),
+    ("size", SIZE_RANGE),
+    ("mtime", MTIME_RANGE),
+    ("chksum", CKSUM_RANGE),
+    ("typeflag", TYPEFLAG_RANGE),
+    ("linkname", LINKNAME_RANGE),
+    ("magic", MAGIC_RANGE),
+    ("version", VERSION_RANGE),
+    ("uname", UNAME_RANGE),
+    ("gname", GNAME_RANGE),
+    ("devmajor", DEVMAJOR_RANGE),
+    ("devminor", DEVMINOR_RANGE),
+    ("prefix", PREFIX_RANGE),
+    ("padding", USTAR_PADDING_RANGE),
+];
+
+/// Names of old GNU header fields, which replace the prefix.
+pub const GNU_FIELDS: [(&str, Range<usize>); 24] = [
+    ("name", NAME_RANGE),
+    ("mode", MODE_RANGE),
+    ("uid", UID_RANGE),
+    ("gid", GID_RANGE),
+    ("size", SIZE_RANGE),
+    ("mtime", MTIME_RANGE),
+    ("chksum", CKSUM_RANGE),
+    ("typeflag", TYPEFLAG_RANGE),
+    ("linkname", LINKNAME_RANGE),
+    ("magic", MAGIC_RANGE),
+    ("version", VERSION_RANGE),
+    ("uname", UNAME_RANGE),
+    ("gname", GNAME_RANGE),
+    ("devmajor", DEVMAJOR_RANGE),
+    ("devminor", DEVMINOR_RANGE),
+    ("atime", 345..357),
+    ("ctime", 357..369),
+    ("offset", 369..381),
+    ("longnames", 381..385),
+    ("unused", 385..386),
+    ("sparse", 386..GNU_SPARSE_EXTENDED),
+    ("isextended", GNU_SPARSE_EXTENDED..GNU_SPARSE_EXTENDED + 1),
+    ("realsize", 483..495),
+    ("padding", 495..BLOCK_SIZE),
+];
+
+/// Names of V7 header fields, which end after the link name.
+pub const V7_FIELDS: [(&str, Range<usize>); 10] = [
+    ("name", NAME_RANGE),
+    ("mode", MODE_RANGE),
+    ("uid", UID_RANGE),
+    ("gid", GID_RANGE),
+    ("size", SIZE_RANGE),
+    ("mtime", MTIME_RANGE),
+    ("chksum", CKSUM_RANGE),
+    ("typeflag", TYPEFLAG_RANGE),
+    ("linkname", LINKNAME_RANGE),
+    ("padding", MAGIC_RANGE.start..BLOCK_SIZE),
+];
+
+/// Returns the header fields of block based on its magic and version.
+/// Blocks with unknown magic are treated as ustar headers.
+pub fn fields(block: &[u8; BLOCK_SIZE]) -> &'static [(&'static str, Range<usize>)] {
+    match &block[MAGIC_RANGE.start..VERSION_RANGE.end] {
+        b"ustar  \0" => &GNU_FIELDS,
+        [0, 0, 0, 0, 0, 0, 0, 0] => &V7_FIELDS,
+        _ => &FIELDS,
+    }
+}
+
+/// Returns the name of the header field of block containing given byte.
+pub fn field_name(block: &[u8; BLOCK_SIZE], offset: usize) -> Option<&'static str> {
+    fields(block)
+        .iter()
+        .find(|(_, range)| range.contains(&offset))
+        .map(|(name, _)| *name)
+}
+
+/// Largest size of extended headers and GNU long names, which are read
+/// into memory.
+pub const MAX_EXTENDED_SIZE: u64 = 1024 * 1024;
+
+/// Offset of the flag in old GNU sparse headers and their extension blocks
+/// which signals that another extension block follows.
+pub const GNU_SPARSE_EXTENDED: usize = 482;
+pub const GNU_SPARSE_EXTENSION_EXTENDED: usize = 504;
+
+/// Typeflags of GNU tar and Solaris tar known to alquitran.
+pub const VENDOR_TYPEFLAGS: &[u8] = b"ADEIKLMNSVX";
+
+/// Sum which matched the checksum field.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Checksum {
+    Invalid,
+    /// Sum of signed bytes, written by historic Sun tar.
+    Signed,
+    Unsigned,
+}
+
 #[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq)]
 pub enum Format {
     Gnu,
@@ -38,17 +141,36 @@ pub enum Format {
     V7,
 }
 
+#[derive(Clone)]
 pub struct LintHeader {
     pub hints: BTreeSet<Hint>,
     pub issues: BTreeSet<Issue>,
     pub bytes: [u8; BLOCK_SIZE],
     pub marks: [u8; BLOCK_SIZE],
+    /// Issues with the header field they concern.
+    pub locations: Vec<(Issue, Range<usize>)>,
+    pub checksum: Checksum,
+    pub devmajor: u64,
+    pub devminor: u64,
     pub format: Format,
+    pub gid: u64,
+    pub gname: String,
     pub linkname: String,
     pub mode: u64,
+    pub mtime: u64,
+    /// Nanoseconds of modification time, only available through pax.
+    pub mtime_nanos: u32,
     pub path: String,
+    /// Records of preceding pax extended header.
+    pub pax: BTreeMap<String, Vec<u8>>,
+    /// Link name as stored in the archive, before normalization.
+    pub raw_linkname: String,
+    /// Path as stored in the archive, before normalization.
+    pub raw_path: String,
     pub size: u64,
     pub typeflag: u8,
+    pub uid: u64,
+    pub uname: String,
 }
 
 impl LintHeader {
@@ -57,47 +179,131 @@ impl LintHeader {
             hints: BTreeSet::new(),
             issues: BTreeSet::new(),
             bytes,
+            locations: Vec::new(),
+            checksum: Checksum::Invalid,
+            devmajor: 0,
+            devminor: 0,
             format: Format::V7,
+            gid: 0,
+            gname: "".to_string(),
             linkname: "".to_string(),
             marks: [0; BLOCK_SIZE],
             mode: 0,
+            mtime: 0,
+            mtime_nanos: 0,
             path: "".to_string(),
+            pax: BTreeMap::new(),
+            raw_linkname: "".to_string(),
+            raw_path: "".to_string(),
             size: 0,
             typeflag: b'0',
+            uid: 0,
+            uname: "".to_string(),
         };
         result.lint();
         result
     }
 
+    /// Applies names of preceding GNU long name and long link headers,
+    /// normalized like paths of ustar headers.
+    pub fn apply_long_names(&mut self, path: Option<String>, linkname: Option<String>) {
+        if path.is_none() && linkname.is_none() {
+            return;
+        }
+        if let Some(path) = path {
+            self.path = normalize(path.clone());
+            self.raw_path = path;
+        }
+        if let Some(linkname) = linkname {
+            self.linkname = normalize(linkname.clone());
+            self.raw_linkname = linkname;
+        }
+        self.lint_full_path();
+        self.lint_linkname();
+    }
+
+    /// Applies records of a preceding pax extended header.
+    pub fn apply_pax(&mut self, records: &BTreeMap<String, Vec<u8>>) {
+        for (keyword, value) in records.iter() {
+            let value = String::from_utf8_lossy(value);
+            match keyword.as_str() {
+                "gid" => self.gid = value.parse().unwrap_or(self.gid),
+                "gname" => self.gname = value.to_string(),
+                "linkpath" => {
+                    self.linkname = normalize(value.to_string());
+                    self.raw_linkname = value.to_string();
+                }
+                "mtime" => {
+                    let (seconds, fraction) = value.split_once('.').unwrap_or((&value, ""));
+                    if let Ok(n) = seconds.parse() {
+                        self.mtime = n;
+                        self.mtime_nanos = format!("{:0<9.9}", fraction).parse().unwrap_or(0);
+                    }
+                }
+                "path" => {
+                    self.path = normalize(value.to_string());
+                    self.raw_path = value.to_string();
+                }
+                "size" => self.size = value.parse().unwrap_or(self.size),
+                "uid" => self.uid = value.parse().unwrap_or(self.uid),
+                "uname" => self.uname = value.to_string(),
+                _ => {}
+            }
+        }
+        if records.contains_key("path") || records.contains_key("linkpath") {
+            self.lint_full_path();
+            self.lint_linkname();
+        }
+        self.pax = records.clone();
+    }
+
     pub fn get_data_block_count(&self) -> u64 {
         if self.typeflag == b'1' || self.typeflag == b'2' || self.typeflag == b'5' {
             return 0;
         }
+        /*
+         * Inode only entries contain meta data of a file but not its
+         * data, while size is still the size of the file.
+         */
+        if self.typeflag == b'I' {
+            return 0;
+        }
         (self.size + 511) / BLOCK_SIZE as u64
     }
 
-    fn insert<T>(&mut self, result: LintResult<T>, offset: usize) -> Option<T> {
+    fn insert<T>(&mut self, result: LintResult<T>, range: Range<usize>) -> Option<T> {
+        let offset = range.start;
         for &(hint, pos) in result.hints.iter() {
             self.hints.insert(hint);
             self.marks[offset + pos] |= WARNING;
         }
         for &(issue, pos) in result.issues.iter() {
-            self.issues.insert(issue);
+            self.report(issue, range.clone());
             self.marks[offset + pos] |= ERROR;
         }
         result.value
     }
 
+    /// Returns true if issues prevent reading past this header. Checksums
+    /// which are only valid as signed sum or in an unusual format are
+    /// accepted by most readers and do not.
+    pub fn is_fatal(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|i| !matches!(i, Issue::SignedChecksum | Issue::UnusualChecksumFormat))
+    }
+
+    /// Records an issue and the header field it concerns.
+    pub fn report(&mut self, issue: Issue, range: Range<usize>) {
+        self.issues.insert(issue);
+        if !self.locations.contains(&(issue, range.clone())) {
+            self.locations.push((issue, range));
+        }
+    }
+
     fn lint(&mut self) {
-        let calculated = calculate_checksum(&self.bytes[..]);
-        let cksum = self.lint_number(CKSUM_RANGE);
-        let sum = match cksum {
-            Some(n) => n,
-            None => calculated + 1,
-        };
-        if sum != calculated {
-            self.mark(CKSUM_RANGE, ERROR);
-            self.issues.insert(Issue::InvalidChecksum);
+        self.lint_checksum();
+        if self.checksum == Checksum::Invalid {
             return;
         }
 
@@ -106,38 +312,39 @@ impl LintHeader {
         if let Some(n) = mode {
             if n > 0o7777 {
                 self.mark(MODE_RANGE, ERROR);
-                self.issues.insert(Issue::InvalidMode);
+                self.report(Issue::InvalidMode, MODE_RANGE);
             }
             self.mode = n;
         }
-        let _uid = self.lint_number(UID_RANGE);
-        let _gid = self.lint_number(GID_RANGE);
+        self.uid = self.lint_number(UID_RANGE).unwrap_or(0);
+        self.gid = self.lint_number(GID_RANGE).unwrap_or(0);
         let size = self.lint_number(SIZE_RANGE);
         match size {
             Some(n) => {
                 if n > 0x7FFFFFFF {
                     self.mark(SIZE_RANGE, ERROR);
-                    self.issues.insert(Issue::LargeEntry);
+                    self.report(Issue::LargeEntry, SIZE_RANGE);
                 }
                 self.size = n;
             }
             None => self.size = 0,
         }
-        let _mtime = self.lint_number(MTIME_RANGE);
+        self.mtime = self.lint_number(MTIME_RANGE).unwrap_or(0);
         self.typeflag = self.bytes[TYPEFLAG_RANGE.start];
         if (self.typeflag < b'0' || self.typeflag > b'7')
             && self.typeflag != b'g'
             && self.typeflag != b'x'
             && self.typeflag != 0
+            && !VENDOR_TYPEFLAGS.contains(&self.typeflag)
         {
             self.marks[TYPEFLAG_RANGE.start] |= ERROR;
-            self.issues.insert(Issue::InvalidTypeFlag);
+            self.report(Issue::InvalidTypeFlag, TYPEFLAG_RANGE);
             self.typeflag = b'0';
         }
 
         if self.typeflag == b'g' {
             self.marks[TYPEFLAG_RANGE.start] |= ERROR;
-            self.issues.insert(Issue::PaxGlobalHeader);
+            self.report(Issue::PaxGlobalHeader, TYPEFLAG_RANGE);
         }
 
         if self.typeflag == 0 {
@@ -164,15 +371,15 @@ impl LintHeader {
         } else {
             if self.bytes[MAGIC_RANGE] != b"ustar\0"[..] {
                 self.mark(MAGIC_RANGE, ERROR);
-                self.issues.insert(Issue::InvalidMagic);
+                self.report(Issue::InvalidMagic, MAGIC_RANGE);
             }
             if self.bytes[VERSION_RANGE] != b"00"[..] {
                 self.mark(VERSION_RANGE, ERROR);
-                self.issues.insert(Issue::InvalidVersion);
+                self.report(Issue::InvalidVersion, VERSION_RANGE);
             }
         }
-        let _uname = self.lint_string(UNAME_RANGE);
-        let _gname = self.lint_string(GNAME_RANGE);
+        self.uname = to_string(self.lint_string(UNAME_RANGE));
+        self.gname = to_string(self.lint_string(GNAME_RANGE));
 
         /*
          * The devmajor and devminor fields are number fields and
@@ -182,37 +389,53 @@ impl LintHeader {
          * do not set them. Accept this for now. At least a pax header
          * itself cannot be a char or block special file type.
          */
-        if
+        let device = self.typeflag == b'3' || self.typeflag == b'4';
+        if device && self.format != Format::V7 {
+            self.devmajor = self.lint_number(DEVMAJOR_RANGE).unwrap_or(0);
+            self.devminor = self.lint_number(DEVMINOR_RANGE).unwrap_or(0);
+        } else if
         /*self.format == Format::Pax ||*/
         self.format == Format::Ustar {
             let devmajor = self.lint_number(DEVMAJOR_RANGE);
-            if let Some(n) = devmajor {
-                if n != 0 {
-                    self.mark(DEVMAJOR_RANGE, ERROR);
-                    self.issues.insert(Issue::DevMajorWithoutSpecialFile);
-                }
+            if let Some(n) = devmajor
+                && n != 0
+            {
+                self.mark(DEVMAJOR_RANGE, ERROR);
+                self.report(Issue::DevMajorWithoutSpecialFile, DEVMAJOR_RANGE);
             }
             let devminor = self.lint_number(DEVMINOR_RANGE);
-            if let Some(n) = devminor {
-                if n != 0 {
-                    self.mark(DEVMINOR_RANGE, ERROR);
-                    self.issues.insert(Issue::DevMinorWithoutSpecialFile);
-                }
+            if let Some(n) = devminor
+                && n != 0
+            {
+                self.mark(DEVMINOR_RANGE, ERROR);
+                self.report(Issue::DevMinorWithoutSpecialFile, DEVMINOR_RANGE);
             }
         }
-        let prefix = self.lint_path(PREFIX_RANGE);
-        let _padding = self.lint_nul(USTAR_PADDING_RANGE);
+        /*
+         * GNU sparse and multi-volume headers store offsets and sparse
+         * maps instead of a prefix.
+         */
+        let gnu_extended = self.typeflag == b'M' || self.typeflag == b'S';
+        let prefix = if self.format == Format::Gnu && gnu_extended {
+            None
+        } else {
+            let prefix = self.lint_path(PREFIX_RANGE);
+            let _padding = self.lint_nul(USTAR_PADDING_RANGE);
+            prefix
+        };
 
         if name.is_none() && self.typeflag != b'5' {
             self.mark(NAME_RANGE, ERROR);
-            self.issues.insert(Issue::EmptyName);
+            self.report(Issue::EmptyName, NAME_RANGE);
         }
 
-        self.path = to_path(prefix, name);
+        self.raw_path = to_path(prefix, name);
+        self.path = normalize(self.raw_path.clone());
         self.linkname = match linkname {
             Some(n) => String::from_utf8(n).unwrap(),
             None => "".to_string(),
         };
+        self.raw_linkname = self.linkname.clone();
 
         self.lint_full_path();
         self.lint_linkname();
@@ -224,9 +447,9 @@ impl LintHeader {
             self.mark(NAME_RANGE, ERROR);
             self.mark(PREFIX_RANGE, ERROR);
             if self.path.starts_with('/') {
-                self.issues.insert(Issue::AbsolutePath);
+                self.report(Issue::AbsolutePath, NAME_RANGE);
             } else {
-                self.issues.insert(Issue::EmptyPath);
+                self.report(Issue::EmptyPath, NAME_RANGE);
             }
         }
         if self.path.contains("/../")
@@ -237,22 +460,23 @@ impl LintHeader {
             self.mark(NAME_RANGE, ERROR);
             self.mark(PREFIX_RANGE, ERROR);
             if self.path.contains("//") {
-                self.issues.insert(Issue::MultiSlashPath);
+                self.report(Issue::MultiSlashPath, NAME_RANGE);
             } else {
-                self.issues.insert(Issue::DotDotPath);
+                self.report(Issue::DotDotPath, NAME_RANGE);
             }
         }
-        if self.path.ends_with('/') && self.typeflag != b'5' {
+        let directory = self.typeflag == b'5' || self.typeflag == b'D';
+        if self.path.ends_with('/') && !directory {
             self.mark(NAME_RANGE, ERROR);
             self.mark(PREFIX_RANGE, ERROR);
             self.mark(TYPEFLAG_RANGE, ERROR);
-            self.issues.insert(Issue::RegularDirectory);
+            self.report(Issue::RegularDirectory, TYPEFLAG_RANGE);
         }
-        if !self.path.ends_with('/') && self.typeflag == b'5' {
+        if !self.path.ends_with('/') && directory {
             self.mark(NAME_RANGE, ERROR);
             self.mark(PREFIX_RANGE, ERROR);
             self.mark(TYPEFLAG_RANGE, ERROR);
-            self.issues.insert(Issue::DirectoryWithoutSlash);
+            self.report(Issue::DirectoryWithoutSlash, TYPEFLAG_RANGE);
         }
     }
 
@@ -260,7 +484,7 @@ impl LintHeader {
         if !self.linkname.is_empty() && self.typeflag != b'1' && self.typeflag != b'2' {
             self.mark(LINKNAME_RANGE, ERROR);
             self.marks[TYPEFLAG_RANGE.start] |= ERROR;
-            self.issues.insert(Issue::LinknameForNonLink);
+            self.report(Issue::LinknameForNonLink, LINKNAME_RANGE);
         }
 
         if self.linkname == self.path && self.typeflag == b'1' {
@@ -268,26 +492,65 @@ impl LintHeader {
             self.mark(NAME_RANGE, ERROR);
             self.mark(PREFIX_RANGE, ERROR);
             self.marks[TYPEFLAG_RANGE.start] |= ERROR;
-            self.issues.insert(Issue::LinkToItself);
+            self.report(Issue::LinkToItself, LINKNAME_RANGE);
         }
     }
 
     fn lint_nul(&mut self, range: Range<usize>) -> Option<bool> {
-        let offset = range.start;
-        let result = lint_nul_field(&self.bytes[range]);
-        self.insert(result, offset)
+        let result = lint_nul_field(&self.bytes[range.clone()]);
+        self.insert(result, range)
+    }
+
+    fn lint_checksum(&mut self) {
+        let unsigned = calculate_checksum(&self.bytes[..]);
+        let signed = calculate_signed_checksum(&self.bytes[..]);
+        let matches = |n: u64| n == unsigned || n as i64 == signed;
+        let result = lint_number_field(&self.bytes[CKSUM_RANGE]);
+        /*
+         * Some writers pad the checksum with leading spaces or omit
+         * the terminator. Most readers accept these fields, so they
+         * are not reported as corrupt if their value matches.
+         */
+        let sum = match result.value {
+            Some(n) => {
+                self.insert(result, CKSUM_RANGE);
+                Some(n)
+            }
+            None => match parse_checksum(&self.bytes[CKSUM_RANGE]) {
+                Some(n) if matches(n) => {
+                    self.mark(CKSUM_RANGE, ERROR);
+                    self.report(Issue::UnusualChecksumFormat, CKSUM_RANGE);
+                    Some(n)
+                }
+                _ => {
+                    self.insert(result, CKSUM_RANGE);
+                    None
+                }
+            },
+        };
+        self.checksum = match sum {
+            Some(n) if n == unsigned => Checksum::Unsigned,
+            Some(n) if n as i64 == signed => {
+                self.mark(CKSUM_RANGE, ERROR);
+                self.report(Issue::SignedChecksum, CKSUM_RANGE);
+                Checksum::Signed
+            }
+            _ => {
+                self.mark(CKSUM_RANGE, ERROR);
+                self.report(Issue::InvalidChecksum, CKSUM_RANGE);
+                Checksum::Invalid
+            }
+        };
     }
 
     fn lint_number(&mut self, range: Range<usize>) -> Option<u64> {
-        let offset = range.start;
-        let result = lint_number_field(&self.bytes[range]);
-        self.insert(result, offset)
+        let result = lint_number_field(&self.bytes[range.clone()]);
+        self.insert(result, range)
     }
 
     fn lint_path(&mut self, range: Range<usize>) -> Option<Vec<u8>> {
-        let offset = range.start;
-        let result = lint_path_field(&self.bytes[range]);
-        self.insert(result, offset)
+        let result = lint_path_field(&self.bytes[range.clone()]);
+        self.insert(result, range)
     }
 
     fn lint_size(&mut self) {
@@ -295,14 +558,18 @@ impl LintHeader {
         if no_data && self.size != 0 {
             self.mark(SIZE_RANGE, ERROR);
             self.marks[TYPEFLAG_RANGE.start] |= ERROR;
-            self.issues.insert(Issue::NoDataWithSize);
+            self.report(Issue::NoDataWithSize, SIZE_RANGE);
+        }
+        let extended = matches!(self.typeflag, b'g' | b'x' | b'K' | b'L' | b'X');
+        if extended && self.size > MAX_EXTENDED_SIZE {
+            self.mark(SIZE_RANGE, ERROR);
+            self.report(Issue::ExtendedHeaderTooLarge, SIZE_RANGE);
         }
     }
 
     fn lint_string(&mut self, range: Range<usize>) -> Option<Vec<u8>> {
-        let offset = range.start;
-        let result = lint_string_field(&self.bytes[range]);
-        self.insert(result, offset)
+        let result = lint_string_field(&self.bytes[range.clone()]);
+        self.insert(result, range)
     }
 
     fn mark(&mut self, range: Range<usize>, b: u8) {
@@ -312,7 +579,26 @@ impl LintHeader {
     }
 }
 
-fn calculate_checksum(bytes: &[u8]) -> u64 {
+/// Returns the issue describing a vendor specific or reserved typeflag.
+pub fn vendor_issue(typeflag: u8) -> Option<Issue> {
+    match typeflag {
+        b'7' => Some(Issue::ContiguousFile),
+        b'A' => Some(Issue::SolarisAcl),
+        b'D' => Some(Issue::GnuDumpDir),
+        b'E' => Some(Issue::SolarisExtendedAttribute),
+        b'I' => Some(Issue::SolarisInode),
+        b'K' => Some(Issue::GnuLongLink),
+        b'L' => Some(Issue::GnuLongName),
+        b'M' => Some(Issue::GnuMultiVolume),
+        b'N' => Some(Issue::GnuOldLongNames),
+        b'S' => Some(Issue::GnuSparse),
+        b'V' => Some(Issue::GnuVolumeLabel),
+        b'X' => Some(Issue::SolarisExtendedHeader),
+        _ => None,
+    }
+}
+
+pub fn calculate_checksum(bytes: &[u8]) -> u64 {
     let empty: [u8; 8] = [b' '; 8];
     bytes[0..148]
         .iter()
@@ -321,7 +607,27 @@ fn calculate_checksum(bytes: &[u8]) -> u64 {
         .fold(0, |a, b| a + (*b as u64))
 }
 
-fn normalize(path: String) -> String {
+/// Calculates checksum like historic Sun tar which treats bytes as signed.
+pub fn calculate_signed_checksum(bytes: &[u8]) -> i64 {
+    let empty: [u8; 8] = [b' '; 8];
+    bytes[0..148]
+        .iter()
+        .chain(&empty[..])
+        .chain(&bytes[156..BLOCK_SIZE])
+        .fold(0, |a, b| a + (*b as i8 as i64))
+}
+
+/// Parses checksum field with leading spaces or without terminator.
+fn parse_checksum(bytes: &[u8]) -> Option<u64> {
+    let field = String::from_utf8_lossy(bytes);
+    let digits = field.trim_start_matches(' ').trim_end_matches(['\0', ' ']);
+    if digits.is_empty() || !digits.bytes().all(|b| (b'0'..=b'7').contains(&b)) {
+        return None;
+    }
+    u64::from_str_radix(digits, 8).ok()
+}
+
+pub fn normalize(path: String) -> String {
     let mut simple = path.clone();
     while simple.contains("//") {
         simple = simple.replace("//", "/");
@@ -344,6 +650,13 @@ fn normalize(path: String) -> String {
     normalized
 }
 
+fn to_string(field: Option<Vec<u8>>) -> String {
+    match field {
+        Some(n) => String::from_utf8_lossy(&n).to_string(),
+        None => "".to_string(),
+    }
+}
+
 fn to_path(prefix: Option<Vec<u8>>, name: Option<Vec<u8>>) -> String {
     let path;
     let prefix_string = match prefix {
@@ -361,7 +674,7 @@ fn to_path(prefix: Option<Vec<u8>>, name: Option<Vec<u8>>) -> String {
     } else {
         path = prefix_string + "/" + &name_string;
     }
-    normalize(path)
+    path
 }
 
 #[cfg(test)]
@@ -1,22 +1,53 @@
+use crate::rule::Violation;
+use core::ops::Range;
 use std::collections::BTreeSet;
+use std::fmt;
 
-#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq)]
+const USTAR: &str = "POSIX.1-2017, XCU pax, ustar Interchange Format";
+const PAX_EXTENDED_HEADER: &str = "POSIX.1-2017, XCU pax, pax Extended Header";
+const PATHNAME_RESOLUTION: &str = "POSIX.1-2017, XBD 4.13 Pathname Resolution";
+
+#[derive(Copy, Clone, Debug, PartialOrd, Ord, PartialEq, Eq)]
 pub enum Hint {
+    ExcessPadding,
+    FractionalTimestamp,
+    PartialRecord,
     UnportableCharInPath,
     UnportableCharInString,
 }
 
-#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialOrd, Ord, PartialEq, Eq)]
 pub enum Issue {
     AbsolutePath,
+    ConcatenatedArchive,
     DataPaddingNotNul,
     DevMajorWithoutSpecialFile,
     DevMinorWithoutSpecialFile,
+    DirectoryNotSearchable,
+    DirectoryReplacesFile,
     DirectoryWithoutSlash,
     DotDotPath,
     EmptyName,
     EmptyPath,
+    ExecutableMode,
+    FileReplacesDirectory,
     FormatChanged,
+    FutureTimestamp,
+    GnuDumpDir,
+    GnuLongLink,
+    GnuLongName,
+    GnuMultiVolume,
+    GnuOldLongNames,
+    GnuSparse,
+    GnuVolumeLabel,
+    GroupWritableMode,
+    HardLinkNotNormalized,
+    HardLinkTargetMissing,
+    HardLinkToDirectory,
+    HardLinkToLink,
+    HardLinkToSpecialFile,
+    ImplementationDivergence,
+    InconsistentOwner,
     InvalidChecksum,
     InvalidMagic,
     InvalidMode,
@@ -24,20 +55,51 @@ pub enum Issue {
     InvalidTypeFlag,
     InvalidVersion,
     LargeEntry,
+    LargeId,
+    LargeTimestamp,
+    LateParentDirectory,
     LinkToItself,
     LinkIsParent,
     LinknameForNonLink,
+    MissingParentDirectory,
     MultiSlashPath,
+    NonCanonicalMode,
+    NonRootOwner,
+    NonUniformTimestamp,
     NoDataWithSize,
     NoNumber,
+    OwnerName,
+    ParentNotDirectory,
+    PartialBlock,
+    PaxHeaderPid,
     PaxPath,
+    PersonalOwnerName,
     PaxSize,
+    PaxVolatileTime,
     ReadOnlyDirectoryWithEntries,
     RegularDirectory,
+    SetGidMode,
+    SetUidMode,
+    SignedChecksum,
+    SolarisAcl,
+    SolarisExtendedAttribute,
+    SolarisExtendedHeader,
+    SolarisInode,
+    SpecialFile,
+    StickyMode,
+    SymlinkAbsolute,
+    SymlinkCycle,
+    SymlinkDangling,
+    SymlinkEscapesRoot,
     TrailingByteNotNul,
+    UnsortedEntries,
+    UnclampedTimestamp,
     UnterminatedNumber,
     UnterminatedString,
     UnusedByteNotNul,
+    UnusualChecksumFormat,
+    WorldWritableMode,
+    ZeroTimestamp,
     PaxHeaderSizeOctal,
     PaxHeaderSizeTooLarge,
     PaxHeaderSizeInvalid,
@@ -49,20 +111,261 @@ pub enum Issue {
     PaxHeaderKeywordEmpty,
     PaxHeaderKeywordDuplicate,
     PaxHeaderKeywordNoUtf8,
+    PaxInvalidNumber,
+    LargeDeviceNumber,
+    DuplicatePath,
+    ExtendedHeaderTooLarge,
+    RuleViolation,
+    ContiguousFile,
 }
 
-pub fn eprint_issues(issues: &BTreeSet<Issue>) {
-    for issue in issues.iter() {
-        let message = match issue {
+#[derive(Copy, Clone, Debug, PartialOrd, Ord, PartialEq, Eq)]
+pub enum Severity {
+    /// Archive is not portable.
+    Warning,
+    /// Archive is corrupt, unsafe or cannot be extracted reliably.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialOrd, Ord, PartialEq, Eq)]
+pub enum Category {
+    ArchiveStructure,
+    Compatibility,
+    HeaderSyntax,
+    PathSafety,
+    Pax,
+    Policy,
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Category::ArchiveStructure => write!(f, "archive structure"),
+            Category::Compatibility => write!(f, "compatibility"),
+            Category::HeaderSyntax => write!(f, "header syntax"),
+            Category::PathSafety => write!(f, "path safety"),
+            Category::Pax => write!(f, "pax"),
+            Category::Policy => write!(f, "policy"),
+        }
+    }
+}
+
+/// Issue of an entry and the bytes it concerns.
+pub struct Finding {
+    pub issue: Issue,
+    /// Path of affected entry.
+    pub path: String,
+    /// Block offset of affected header.
+    pub offset: usize,
+    /// Index of affected entry, counting extended headers.
+    pub entry: usize,
+    /// Absolute byte range in the archive.
+    pub location: Range<usize>,
+    /// Name of header field, None if location is not in the header.
+    pub field: Option<&'static str>,
+    /// Violated custom rule if issue is `Issue::RuleViolation`.
+    pub rule: Option<Violation>,
+}
+
+impl Finding {
+    /// Returns the code of the violated rule or else of the issue.
+    pub fn code(&self) -> String {
+        match &self.rule {
+            Some(r) => r.code.clone(),
+            None => self.issue.code(),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match &self.rule {
+            Some(r) => &r.message,
+            None => self.issue.message(),
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        match &self.rule {
+            Some(r) => r.severity,
+            None => self.issue.severity(),
+        }
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}] {}", self.code(), self.message())
+    }
+}
+
+pub fn eprint_hints(hints: &BTreeSet<Hint>) {
+    for hint in hints.iter() {
+        let message = match hint {
+            Hint::ExcessPadding => "Padding exceeds record of end of archive marker.",
+            Hint::FractionalTimestamp => "Fractional modification time is lost in ustar.",
+            Hint::PartialRecord => "Archive size is not a multiple of 10240 bytes.",
+            Hint::UnportableCharInPath => "Path contains unportable character(s).",
+            Hint::UnportableCharInString => "String contains unportable character(s).",
+        };
+        eprintln!("-> {}", message);
+    }
+}
+
+impl Issue {
+    /// All issues in order of their codes.
+    pub const ALL: [Issue; 99] = [
+        Issue::AbsolutePath,
+        Issue::DataPaddingNotNul,
+        Issue::DevMajorWithoutSpecialFile,
+        Issue::DevMinorWithoutSpecialFile,
+        Issue::DirectoryWithoutSlash,
+        Issue::DotDotPath,
+        Issue::EmptyName,
+        Issue::EmptyPath,
+        Issue::FormatChanged,
+        Issue::InvalidChecksum,
+        Issue::InvalidMagic,
+        Issue::InvalidMode,
+        Issue::InvalidNumber,
+        Issue::InvalidTypeFlag,
+        Issue::InvalidVersion,
+        Issue::LargeEntry,
+        Issue::LinkToItself,
+        Issue::LinkIsParent,
+        Issue::LinknameForNonLink,
+        Issue::MultiSlashPath,
+        Issue::NoDataWithSize,
+        Issue::NoNumber,
+        Issue::PaxPath,
+        Issue::PaxSize,
+        Issue::ReadOnlyDirectoryWithEntries,
+        Issue::RegularDirectory,
+        Issue::TrailingByteNotNul,
+        Issue::UnterminatedNumber,
+        Issue::UnterminatedString,
+        Issue::UnusedByteNotNul,
+        Issue::PaxHeaderSizeOctal,
+        Issue::PaxHeaderSizeTooLarge,
+        Issue::PaxHeaderSizeInvalid,
+        Issue::PaxGlobalHeader,
+        Issue::PaxEmpty,
+        Issue::PaxHeaderNoNewline,
+        Issue::PaxHeaderNoEqualSign,
+        Issue::PaxHeaderKeywordBlank,
+        Issue::PaxHeaderKeywordEmpty,
+        Issue::PaxHeaderKeywordDuplicate,
+        Issue::PaxHeaderKeywordNoUtf8,
+        Issue::PartialBlock,
+        Issue::ConcatenatedArchive,
+        Issue::SymlinkAbsolute,
+        Issue::SymlinkEscapesRoot,
+        Issue::SymlinkDangling,
+        Issue::SymlinkCycle,
+        Issue::HardLinkTargetMissing,
+        Issue::HardLinkToDirectory,
+        Issue::HardLinkToLink,
+        Issue::HardLinkNotNormalized,
+        Issue::HardLinkToSpecialFile,
+        Issue::ParentNotDirectory,
+        Issue::DirectoryReplacesFile,
+        Issue::FileReplacesDirectory,
+        Issue::MissingParentDirectory,
+        Issue::LateParentDirectory,
+        Issue::SetUidMode,
+        Issue::SetGidMode,
+        Issue::StickyMode,
+        Issue::WorldWritableMode,
+        Issue::GroupWritableMode,
+        Issue::ExecutableMode,
+        Issue::DirectoryNotSearchable,
+        Issue::NonCanonicalMode,
+        Issue::NonRootOwner,
+        Issue::PersonalOwnerName,
+        Issue::LargeId,
+        Issue::InconsistentOwner,
+        Issue::FutureTimestamp,
+        Issue::ZeroTimestamp,
+        Issue::LargeTimestamp,
+        Issue::UnclampedTimestamp,
+        Issue::NonUniformTimestamp,
+        Issue::OwnerName,
+        Issue::PaxVolatileTime,
+        Issue::PaxHeaderPid,
+        Issue::UnsortedEntries,
+        Issue::SpecialFile,
+        Issue::GnuDumpDir,
+        Issue::GnuLongLink,
+        Issue::GnuLongName,
+        Issue::GnuMultiVolume,
+        Issue::GnuOldLongNames,
+        Issue::GnuSparse,
+        Issue::GnuVolumeLabel,
+        Issue::SolarisAcl,
+        Issue::SolarisExtendedAttribute,
+        Issue::SolarisExtendedHeader,
+        Issue::SolarisInode,
+        Issue::ImplementationDivergence,
+        Issue::SignedChecksum,
+        Issue::UnusualChecksumFormat,
+        Issue::PaxInvalidNumber,
+        Issue::LargeDeviceNumber,
+        Issue::DuplicatePath,
+        Issue::ExtendedHeaderTooLarge,
+        Issue::RuleViolation,
+        Issue::ContiguousFile,
+    ];
+
+    pub fn message(&self) -> &'static str {
+        match self {
             Issue::AbsolutePath => "Entry has absolute path name.",
+            Issue::ConcatenatedArchive => {
+                "Another archive follows end of archive marker, only some tools extract it."
+            }
+            Issue::ContiguousFile => "Contiguous file is extracted as regular file.",
             Issue::DataPaddingNotNul => "Data padding byte(s) not nul.",
             Issue::DevMajorWithoutSpecialFile => "Device major is only valid for special file.",
             Issue::DevMinorWithoutSpecialFile => "Device minor is only valid for special file.",
+            Issue::DirectoryNotSearchable => "Directory is not searchable by owner.",
+            Issue::DirectoryReplacesFile => "Directory replaces a previous non-directory entry.",
             Issue::DirectoryWithoutSlash => "Directory without trailing slash encountered.",
             Issue::DotDotPath => "Entry has .. as directory part in path name.",
+            Issue::DuplicatePath => "Path occurs in multiple entries.",
             Issue::EmptyName => "Name field is empty.",
             Issue::EmptyPath => "Name and prefix are empty.",
+            Issue::ExecutableMode => "File without script or binary magic is executable.",
+            Issue::ExtendedHeaderTooLarge => "Extended header is too large.",
+            Issue::FileReplacesDirectory => "Non-directory entry replaces a previous directory.",
             Issue::FormatChanged => "Header format changed within archive.",
+            Issue::GnuDumpDir => "GNU dump directory is only understood by GNU tar.",
+            Issue::GnuLongLink => {
+                "GNU long link name is understood by GNU tar, bsdtar, busybox, Go and Python."
+            }
+            Issue::GnuLongName => {
+                "GNU long name is understood by GNU tar, bsdtar, busybox, Go and Python."
+            }
+            Issue::GnuMultiVolume => "GNU multi-volume continuation is only understood by GNU tar.",
+            Issue::GnuOldLongNames => "GNU old long names entry is obsolete and not understood.",
+            Issue::GnuSparse => "GNU sparse file is understood by GNU tar, bsdtar, Go and Python.",
+            Issue::GnuVolumeLabel => "GNU volume label is only understood by GNU tar and bsdtar.",
+            Issue::GroupWritableMode => "Entry is group writable.",
+            Issue::FutureTimestamp => "Modification time is in the future.",
+            Issue::HardLinkNotNormalized => "Hard link target differs from entry path.",
+            Issue::HardLinkTargetMissing => "Hard link target is no preceding entry.",
+            Issue::HardLinkToDirectory => "Hard link links to a directory.",
+            Issue::HardLinkToLink => "Hard link links to another link.",
+            Issue::HardLinkToSpecialFile => "Hard link links to a special file.",
+            Issue::ImplementationDivergence => {
+                "Implementations disagree about path, type or size of entry."
+            }
+            Issue::InconsistentOwner => "Owner differs from owner of first entry.",
             Issue::InvalidChecksum => "Checksum does not match.",
             Issue::InvalidMagic => "No tar/ustar magic.",
             Issue::InvalidMode => "Mode is invalid.",
@@ -70,20 +373,59 @@ pub fn eprint_issues(issues: &BTreeSet<Issue>) {
             Issue::InvalidTypeFlag => "Typeflag is invalid or not fully portable.",
             Issue::InvalidVersion => "No tar/ustar compatible version.",
             Issue::LargeEntry => "Large entry detected.",
+            Issue::LargeDeviceNumber => "Device number is too large for common systems.",
+            Issue::LargeId => "Id is too large for old implementations.",
+            Issue::LargeTimestamp => "Modification time is too large for ustar headers.",
+            Issue::LateParentDirectory => "Directory entry follows its contents.",
             Issue::LinkIsParent => "A parent path component is a link.",
             Issue::LinkToItself => "Hard link links to itself.",
             Issue::LinknameForNonLink => "Link name for a non-link entry detected.",
+            Issue::MissingParentDirectory => "Parent directory has no entry.",
             Issue::MultiSlashPath => "Entry has consecutive slashes in path name.",
+            Issue::NonCanonicalMode => "Mode is neither 0755 nor 0644.",
+            Issue::NonRootOwner => "Owner or group id is not 0.",
+            Issue::NonUniformTimestamp => {
+                "Modification time differs from first entry, it depends on time of build."
+            }
             Issue::NoDataWithSize => "Entry without data blocks has a size.",
             Issue::NoNumber => "Number field contains no number.",
+            Issue::ParentNotDirectory => "A parent path component is no directory.",
+            Issue::OwnerName => "User or group name is set, it depends on build system.",
+            Issue::PartialBlock => "Archive size is not a multiple of 512 bytes.",
+            Issue::PaxHeaderPid => "Pax header name contains process id of archiver.",
             Issue::PaxPath => "Pax header possibly defines path.",
             Issue::PaxSize => "Pax header possibly defines size.",
+            Issue::PersonalOwnerName => "User or group name may reveal personal information.",
+            Issue::PaxVolatileTime => "Pax header records access or change time of build.",
             Issue::ReadOnlyDirectoryWithEntries => "A parent path component is read-only for user.",
             Issue::RegularDirectory => "Directory has no explicit directory typeflag.",
+            Issue::RuleViolation => "Custom rule is violated.",
+            Issue::SetGidMode => "Entry has setgid bit.",
+            Issue::SetUidMode => "Entry has setuid bit.",
+            Issue::SignedChecksum => "Checksum is only valid as sum of signed bytes.",
+            Issue::SolarisAcl => "Solaris ACL is only understood by Solaris tar and bsdtar.",
+            Issue::SolarisExtendedAttribute => {
+                "Solaris extended attribute is only understood by Solaris tar."
+            }
+            Issue::SolarisExtendedHeader => {
+                "Solaris extended header is understood by Solaris tar, GNU tar, bsdtar and Python."
+            }
+            Issue::SolarisInode => "Inode only entry is only understood by Solaris tar and star.",
+            Issue::SpecialFile => "Device or fifo entries are not portable.",
+            Issue::StickyMode => "Entry has sticky bit.",
+            Issue::SymlinkAbsolute => "Symbolic link points to an absolute path.",
+            Issue::SymlinkCycle => "Symbolic links form a cycle.",
+            Issue::SymlinkDangling => "Symbolic link points to a nonexistent entry.",
+            Issue::SymlinkEscapesRoot => "Symbolic link points outside of archive.",
             Issue::TrailingByteNotNul => "Byte(s) after end of archive not nul.",
+            Issue::UnclampedTimestamp => "Modification time is newer than SOURCE_DATE_EPOCH.",
+            Issue::UnsortedEntries => "Entries are not sorted, order depends on file system.",
             Issue::UnterminatedNumber => "Number field has no terminating character.",
             Issue::UnterminatedString => "String field has no terminating character.",
             Issue::UnusedByteNotNul => "Unused byte(s) not nul.",
+            Issue::UnusualChecksumFormat => "Checksum has leading spaces or no terminator.",
+            Issue::WorldWritableMode => "Entry is world writable.",
+            Issue::ZeroTimestamp => "Modification time is epoch.",
             Issue::PaxHeaderSizeOctal => "Pax header size starts with zero",
             Issue::PaxHeaderSizeTooLarge => "Pax header size is too large.",
             Issue::PaxHeaderSizeInvalid => "Pax header size is invalid.",
@@ -97,7 +439,731 @@ pub fn eprint_issues(issues: &BTreeSet<Issue>) {
                 "Same pax header keyword encountered multiple times."
             }
             Issue::PaxHeaderKeywordNoUtf8 => "Pax header keyword is not UTF-8.",
-        };
-        eprintln!("=> {}", message);
+            Issue::PaxInvalidNumber => "Pax header value is not a valid number.",
+        }
+    }
+
+    /// Returns the stable code of the issue, e.g. AQ0001.
+    pub fn code(&self) -> String {
+        format!("AQ{:04}", self.number())
+    }
+
+    /// Returns the issue with given code.
+    pub fn from_code(code: &str) -> Option<Issue> {
+        Issue::ALL.iter().find(|i| i.code() == code).copied()
+    }
+
+    fn number(&self) -> u16 {
+        match self {
+            Issue::AbsolutePath => 1,
+            Issue::DataPaddingNotNul => 2,
+            Issue::DevMajorWithoutSpecialFile => 3,
+            Issue::DevMinorWithoutSpecialFile => 4,
+            Issue::DirectoryWithoutSlash => 5,
+            Issue::DotDotPath => 6,
+            Issue::EmptyName => 7,
+            Issue::EmptyPath => 8,
+            Issue::FormatChanged => 9,
+            Issue::InvalidChecksum => 10,
+            Issue::InvalidMagic => 11,
+            Issue::InvalidMode => 12,
+            Issue::InvalidNumber => 13,
+            Issue::InvalidTypeFlag => 14,
+            Issue::InvalidVersion => 15,
+            Issue::LargeEntry => 16,
+            Issue::LinkToItself => 17,
+            Issue::LinkIsParent => 18,
+            Issue::LinknameForNonLink => 19,
+            Issue::MultiSlashPath => 20,
+            Issue::NoDataWithSize => 21,
+            Issue::NoNumber => 22,
+            Issue::PaxPath => 23,
+            Issue::PaxSize => 24,
+            Issue::ReadOnlyDirectoryWithEntries => 25,
+            Issue::RegularDirectory => 26,
+            Issue::TrailingByteNotNul => 27,
+            Issue::UnterminatedNumber => 28,
+            Issue::UnterminatedString => 29,
+            Issue::UnusedByteNotNul => 30,
+            Issue::PaxHeaderSizeOctal => 31,
+            Issue::PaxHeaderSizeTooLarge => 32,
+            Issue::PaxHeaderSizeInvalid => 33,
+            Issue::PaxGlobalHeader => 34,
+            Issue::PaxEmpty => 35,
+            Issue::PaxHeaderNoNewline => 36,
+            Issue::PaxHeaderNoEqualSign => 37,
+            Issue::PaxHeaderKeywordBlank => 38,
+            Issue::PaxHeaderKeywordEmpty => 39,
+            Issue::PaxHeaderKeywordDuplicate => 40,
+            Issue::PaxHeaderKeywordNoUtf8 => 41,
+            Issue::PartialBlock => 42,
+            Issue::ConcatenatedArchive => 43,
+            Issue::SymlinkAbsolute => 44,
+            Issue::SymlinkEscapesRoot => 45,
+            Issue::SymlinkDangling => 46,
+            Issue::SymlinkCycle => 47,
+            Issue::HardLinkTargetMissing => 48,
+            Issue::HardLinkToDirectory => 49,
+            Issue::HardLinkToLink => 50,
+            Issue::HardLinkNotNormalized => 51,
+            Issue::HardLinkToSpecialFile => 52,
+            Issue::ParentNotDirectory => 53,
+            Issue::DirectoryReplacesFile => 54,
+            Issue::FileReplacesDirectory => 55,
+            Issue::MissingParentDirectory => 56,
+            Issue::LateParentDirectory => 57,
+            Issue::SetUidMode => 58,
+            Issue::SetGidMode => 59,
+            Issue::StickyMode => 60,
+            Issue::WorldWritableMode => 61,
+            Issue::GroupWritableMode => 62,
+            Issue::ExecutableMode => 63,
+            Issue::DirectoryNotSearchable => 64,
+            Issue::NonCanonicalMode => 65,
+            Issue::NonRootOwner => 66,
+            Issue::PersonalOwnerName => 67,
+            Issue::LargeId => 68,
+            Issue::InconsistentOwner => 69,
+            Issue::FutureTimestamp => 70,
+            Issue::ZeroTimestamp => 71,
+            Issue::LargeTimestamp => 72,
+            Issue::UnclampedTimestamp => 73,
+            Issue::NonUniformTimestamp => 74,
+            Issue::OwnerName => 75,
+            Issue::PaxVolatileTime => 76,
+            Issue::PaxHeaderPid => 77,
+            Issue::UnsortedEntries => 78,
+            Issue::SpecialFile => 79,
+            Issue::GnuDumpDir => 80,
+            Issue::GnuLongLink => 81,
+            Issue::GnuLongName => 82,
+            Issue::GnuMultiVolume => 83,
+            Issue::GnuOldLongNames => 84,
+            Issue::GnuSparse => 85,
+            Issue::GnuVolumeLabel => 86,
+            Issue::SolarisAcl => 87,
+            Issue::SolarisExtendedAttribute => 88,
+            Issue::SolarisExtendedHeader => 89,
+            Issue::SolarisInode => 90,
+            Issue::ImplementationDivergence => 91,
+            Issue::SignedChecksum => 92,
+            Issue::UnusualChecksumFormat => 93,
+            Issue::PaxInvalidNumber => 94,
+            Issue::LargeDeviceNumber => 95,
+            Issue::DuplicatePath => 96,
+            Issue::ExtendedHeaderTooLarge => 97,
+            Issue::RuleViolation => 98,
+            Issue::ContiguousFile => 99,
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        match self {
+            Issue::AbsolutePath
+            | Issue::DataPaddingNotNul
+            | Issue::DotDotPath
+            | Issue::EmptyName
+            | Issue::EmptyPath
+            | Issue::InvalidChecksum
+            | Issue::InvalidMagic
+            | Issue::InvalidMode
+            | Issue::InvalidNumber
+            | Issue::InvalidTypeFlag
+            | Issue::InvalidVersion
+            | Issue::LinkToItself
+            | Issue::LinkIsParent
+            | Issue::NoDataWithSize
+            | Issue::NoNumber
+            | Issue::RegularDirectory
+            | Issue::UnterminatedNumber
+            | Issue::PaxHeaderSizeTooLarge
+            | Issue::PaxHeaderSizeInvalid
+            | Issue::PaxHeaderNoNewline
+            | Issue::PaxHeaderNoEqualSign
+            | Issue::PaxHeaderKeywordEmpty
+            | Issue::PaxHeaderKeywordNoUtf8
+            | Issue::PaxInvalidNumber
+            | Issue::ExtendedHeaderTooLarge
+            | Issue::PartialBlock
+            | Issue::SymlinkEscapesRoot
+            | Issue::HardLinkTargetMissing
+            | Issue::HardLinkToDirectory
+            | Issue::ParentNotDirectory
+            | Issue::DirectoryReplacesFile
+            | Issue::FileReplacesDirectory => Severity::Error,
+            Issue::DevMajorWithoutSpecialFile
+            | Issue::DevMinorWithoutSpecialFile
+            | Issue::DirectoryWithoutSlash
+            | Issue::FormatChanged
+            | Issue::LargeEntry
+            | Issue::LinknameForNonLink
+            | Issue::MultiSlashPath
+            | Issue::PaxPath
+            | Issue::PaxSize
+            | Issue::ReadOnlyDirectoryWithEntries
+            | Issue::TrailingByteNotNul
+            | Issue::UnterminatedString
+            | Issue::UnusedByteNotNul
+            | Issue::PaxHeaderSizeOctal
+            | Issue::PaxGlobalHeader
+            | Issue::PaxEmpty
+            | Issue::PaxHeaderKeywordBlank
+            | Issue::PaxHeaderKeywordDuplicate
+            | Issue::ConcatenatedArchive
+            | Issue::SymlinkAbsolute
+            | Issue::SymlinkDangling
+            | Issue::SymlinkCycle
+            | Issue::HardLinkToLink
+            | Issue::HardLinkNotNormalized
+            | Issue::HardLinkToSpecialFile
+            | Issue::MissingParentDirectory
+            | Issue::LateParentDirectory
+            | Issue::SetUidMode
+            | Issue::SetGidMode
+            | Issue::StickyMode
+            | Issue::WorldWritableMode
+            | Issue::GroupWritableMode
+            | Issue::ExecutableMode
+            | Issue::DirectoryNotSearchable
+            | Issue::NonCanonicalMode
+            | Issue::NonRootOwner
+            | Issue::PersonalOwnerName
+            | Issue::LargeId
+            | Issue::InconsistentOwner
+            | Issue::FutureTimestamp
+            | Issue::ZeroTimestamp
+            | Issue::LargeTimestamp
+            | Issue::UnclampedTimestamp
+            | Issue::NonUniformTimestamp
+            | Issue::OwnerName
+            | Issue::PaxVolatileTime
+            | Issue::PaxHeaderPid
+            | Issue::UnsortedEntries
+            | Issue::SpecialFile
+            | Issue::LargeDeviceNumber
+            | Issue::DuplicatePath
+            | Issue::RuleViolation
+            | Issue::GnuDumpDir
+            | Issue::GnuLongLink
+            | Issue::GnuLongName
+            | Issue::GnuMultiVolume
+            | Issue::GnuOldLongNames
+            | Issue::GnuSparse
+            | Issue::GnuVolumeLabel
+            | Issue::SolarisAcl
+            | Issue::SolarisExtendedAttribute
+            | Issue::SolarisExtendedHeader
+            | Issue::SolarisInode
+            | Issue::ImplementationDivergence
+            | Issue::SignedChecksum
+            | Issue::UnusualChecksumFormat
+            | Issue::ContiguousFile => Severity::Warning,
+        }
+    }
+
+    /// Returns the category of issue. Issues of checks which are only
+    /// enabled through options are policies.
+    pub fn category(&self) -> Category {
+        match self {
+            Issue::AbsolutePath
+            | Issue::DirectoryWithoutSlash
+            | Issue::DotDotPath
+            | Issue::EmptyPath
+            | Issue::LinkToItself
+            | Issue::LinkIsParent
+            | Issue::MultiSlashPath
+            | Issue::ReadOnlyDirectoryWithEntries
+            | Issue::RegularDirectory
+            | Issue::SymlinkAbsolute
+            | Issue::SymlinkEscapesRoot
+            | Issue::SymlinkDangling
+            | Issue::SymlinkCycle
+            | Issue::HardLinkTargetMissing
+            | Issue::HardLinkToDirectory
+            | Issue::HardLinkToLink
+            | Issue::HardLinkNotNormalized
+            | Issue::HardLinkToSpecialFile
+            | Issue::ParentNotDirectory
+            | Issue::DirectoryReplacesFile
+            | Issue::FileReplacesDirectory => Category::PathSafety,
+            Issue::DataPaddingNotNul
+            | Issue::FormatChanged
+            | Issue::TrailingByteNotNul
+            | Issue::PartialBlock
+            | Issue::ConcatenatedArchive
+            | Issue::DuplicatePath
+            | Issue::ExtendedHeaderTooLarge => Category::ArchiveStructure,
+            Issue::DevMajorWithoutSpecialFile
+            | Issue::DevMinorWithoutSpecialFile
+            | Issue::EmptyName
+            | Issue::InvalidChecksum
+            | Issue::InvalidMagic
+            | Issue::InvalidMode
+            | Issue::InvalidNumber
+            | Issue::InvalidTypeFlag
+            | Issue::InvalidVersion
+            | Issue::LargeEntry
+            | Issue::LinknameForNonLink
+            | Issue::NoDataWithSize
+            | Issue::NoNumber
+            | Issue::UnterminatedNumber
+            | Issue::UnterminatedString
+            | Issue::UnusedByteNotNul
+            | Issue::LargeTimestamp
+            | Issue::SignedChecksum
+            | Issue::UnusualChecksumFormat => Category::HeaderSyntax,
+            Issue::PaxPath
+            | Issue::PaxSize
+            | Issue::PaxHeaderSizeOctal
+            | Issue::PaxHeaderSizeTooLarge
+            | Issue::PaxHeaderSizeInvalid
+            | Issue::PaxGlobalHeader
+            | Issue::PaxEmpty
+            | Issue::PaxHeaderNoNewline
+            | Issue::PaxHeaderNoEqualSign
+            | Issue::PaxHeaderKeywordBlank
+            | Issue::PaxHeaderKeywordEmpty
+            | Issue::PaxHeaderKeywordDuplicate
+            | Issue::PaxHeaderKeywordNoUtf8
+            | Issue::PaxInvalidNumber => Category::Pax,
+            Issue::SetUidMode
+            | Issue::SetGidMode
+            | Issue::StickyMode
+            | Issue::WorldWritableMode
+            | Issue::GroupWritableMode
+            | Issue::ExecutableMode
+            | Issue::DirectoryNotSearchable
+            | Issue::NonCanonicalMode
+            | Issue::NonRootOwner
+            | Issue::PersonalOwnerName
+            | Issue::InconsistentOwner
+            | Issue::FutureTimestamp
+            | Issue::ZeroTimestamp
+            | Issue::UnclampedTimestamp
+            | Issue::NonUniformTimestamp
+            | Issue::OwnerName
+            | Issue::MissingParentDirectory
+            | Issue::LateParentDirectory
+            | Issue::UnsortedEntries
+            | Issue::LargeId
+            | Issue::SpecialFile
+            | Issue::PaxVolatileTime
+            | Issue::PaxHeaderPid
+            | Issue::RuleViolation => Category::Policy,
+            Issue::LargeDeviceNumber
+            | Issue::ContiguousFile
+            | Issue::GnuDumpDir
+            | Issue::GnuLongLink
+            | Issue::GnuLongName
+            | Issue::GnuMultiVolume
+            | Issue::GnuOldLongNames
+            | Issue::GnuSparse
+            | Issue::GnuVolumeLabel
+            | Issue::SolarisAcl
+            | Issue::SolarisExtendedAttribute
+            | Issue::SolarisExtendedHeader
+            | Issue::SolarisInode
+            | Issue::ImplementationDivergence => Category::Compatibility,
+        }
+    }
+
+    /// Returns the relevant section of POSIX, if any.
+    pub fn reference(&self) -> Option<&'static str> {
+        match self {
+            Issue::AbsolutePath
+            | Issue::DataPaddingNotNul
+            | Issue::DevMajorWithoutSpecialFile
+            | Issue::DevMinorWithoutSpecialFile
+            | Issue::DirectoryWithoutSlash
+            | Issue::EmptyName
+            | Issue::EmptyPath
+            | Issue::FormatChanged
+            | Issue::InvalidChecksum
+            | Issue::InvalidMagic
+            | Issue::InvalidMode
+            | Issue::InvalidNumber
+            | Issue::InvalidTypeFlag
+            | Issue::InvalidVersion
+            | Issue::LargeEntry
+            | Issue::LinkToItself
+            | Issue::LinknameForNonLink
+            | Issue::NoDataWithSize
+            | Issue::NoNumber
+            | Issue::ReadOnlyDirectoryWithEntries
+            | Issue::RegularDirectory
+            | Issue::TrailingByteNotNul
+            | Issue::UnterminatedNumber
+            | Issue::UnterminatedString
+            | Issue::UnusedByteNotNul
+            | Issue::PartialBlock
+            | Issue::ConcatenatedArchive
+            | Issue::HardLinkTargetMissing
+            | Issue::HardLinkToDirectory
+            | Issue::HardLinkToLink
+            | Issue::HardLinkNotNormalized
+            | Issue::HardLinkToSpecialFile
+            | Issue::DirectoryReplacesFile
+            | Issue::FileReplacesDirectory
+            | Issue::MissingParentDirectory
+            | Issue::LateParentDirectory
+            | Issue::SetUidMode
+            | Issue::SetGidMode
+            | Issue::StickyMode
+            | Issue::WorldWritableMode
+            | Issue::GroupWritableMode
+            | Issue::ExecutableMode
+            | Issue::DirectoryNotSearchable
+            | Issue::NonCanonicalMode
+            | Issue::NonRootOwner
+            | Issue::PersonalOwnerName
+            | Issue::LargeId
+            | Issue::InconsistentOwner
+            | Issue::FutureTimestamp
+            | Issue::ZeroTimestamp
+            | Issue::LargeTimestamp
+            | Issue::SpecialFile
+            | Issue::LargeDeviceNumber
+            | Issue::SignedChecksum
+            | Issue::UnusualChecksumFormat
+            | Issue::ContiguousFile => Some(USTAR),
+            Issue::DotDotPath
+            | Issue::LinkIsParent
+            | Issue::MultiSlashPath
+            | Issue::SymlinkAbsolute
+            | Issue::SymlinkEscapesRoot
+            | Issue::SymlinkDangling
+            | Issue::SymlinkCycle
+            | Issue::ParentNotDirectory => Some(PATHNAME_RESOLUTION),
+            Issue::PaxPath
+            | Issue::PaxSize
+            | Issue::PaxHeaderSizeOctal
+            | Issue::PaxHeaderSizeTooLarge
+            | Issue::PaxHeaderSizeInvalid
+            | Issue::PaxGlobalHeader
+            | Issue::PaxEmpty
+            | Issue::PaxHeaderNoNewline
+            | Issue::PaxHeaderNoEqualSign
+            | Issue::PaxHeaderKeywordBlank
+            | Issue::PaxHeaderKeywordEmpty
+            | Issue::PaxHeaderKeywordDuplicate
+            | Issue::PaxHeaderKeywordNoUtf8
+            | Issue::PaxInvalidNumber
+            | Issue::PaxVolatileTime
+            | Issue::PaxHeaderPid => Some(PAX_EXTENDED_HEADER),
+            Issue::UnclampedTimestamp
+            | Issue::NonUniformTimestamp
+            | Issue::OwnerName
+            | Issue::UnsortedEntries
+            | Issue::ImplementationDivergence
+            | Issue::DuplicatePath
+            | Issue::ExtendedHeaderTooLarge
+            | Issue::RuleViolation => None,
+            Issue::GnuDumpDir
+            | Issue::GnuLongLink
+            | Issue::GnuLongName
+            | Issue::GnuMultiVolume
+            | Issue::GnuOldLongNames
+            | Issue::GnuSparse
+            | Issue::GnuVolumeLabel
+            | Issue::SolarisAcl
+            | Issue::SolarisExtendedAttribute
+            | Issue::SolarisExtendedHeader
+            | Issue::SolarisInode => Some(USTAR),
+        }
+    }
+
+    /// Returns a longer explanation of the issue and its consequences.
+    pub fn explanation(&self) -> &'static str {
+        match self {
+            Issue::AbsolutePath => {
+                "Extractors either strip the leading slash or write outside of the current directory. Archives should only contain relative paths."
+            }
+            Issue::DataPaddingNotNul => {
+                "The last data block of an entry is padded with nul bytes. Other bytes indicate a corrupt archive or leak data of the archiver."
+            }
+            Issue::DevMajorWithoutSpecialFile => {
+                "The devmajor field is only meaningful for character and block devices and should be empty or zero for other entries."
+            }
+            Issue::DevMinorWithoutSpecialFile => {
+                "The devminor field is only meaningful for character and block devices and should be empty or zero for other entries."
+            }
+            Issue::DirectoryWithoutSlash => {
+                "Directory entries traditionally end with a slash. Old implementations rely on the slash instead of the typeflag."
+            }
+            Issue::DotDotPath => {
+                "A .. component lets an entry escape the extraction directory. Many extractors refuse such entries, others overwrite files outside of the target."
+            }
+            Issue::EmptyName => {
+                "The name field must contain at least the last path component of the entry."
+            }
+            Issue::EmptyPath => "An entry without name and prefix cannot be extracted.",
+            Issue::FormatChanged => {
+                "Mixing header formats within an archive, e.g. GNU and ustar headers, is handled differently by implementations."
+            }
+            Issue::InvalidChecksum => {
+                "The checksum is the sum of all header bytes with the checksum field taken as spaces. A mismatch means the header is corrupt or no tar header at all."
+            }
+            Issue::InvalidMagic => {
+                "The magic field must contain ustar followed by a nul byte. Without it, readers fall back to the old v7 format or stop."
+            }
+            Issue::InvalidMode => {
+                "The mode field may only contain the permission bits, setuid, setgid and sticky bit, i.e. at most 07777."
+            }
+            Issue::InvalidNumber => {
+                "Numeric fields contain octal digits terminated by a space or nul byte. Other characters are interpreted differently by implementations."
+            }
+            Issue::InvalidTypeFlag => {
+                "The typeflag is neither defined by POSIX nor a known vendor extension. POSIX requires unknown types to be extracted as regular files but not all readers do so."
+            }
+            Issue::InvalidVersion => "The version field must contain 00 for ustar archives.",
+            Issue::LargeEntry => {
+                "Sizes above 2 GiB are not supported by all implementations, even though the octal size field can hold up to 8 GiB."
+            }
+            Issue::LinkToItself => {
+                "A hard link to itself cannot be created and replaces the entry with nothing."
+            }
+            Issue::LinkIsParent => {
+                "An entry below a link is extracted through that link, possibly outside of the extraction directory."
+            }
+            Issue::LinknameForNonLink => {
+                "The linkname field is only meaningful for hard and symbolic links."
+            }
+            Issue::MultiSlashPath => {
+                "Consecutive slashes are equivalent to a single slash, but implementations compare paths differently, e.g. when detecting duplicates or link targets."
+            }
+            Issue::NoDataWithSize => {
+                "Links and directories have no data, but some implementations still skip size bytes. Readers disagree about where the next header starts."
+            }
+            Issue::NoNumber => "Numeric fields must contain at least one octal digit.",
+            Issue::PaxPath => {
+                "The pax header contains a record which may override the path. Readers without pax support extract the entry with its ustar name instead."
+            }
+            Issue::PaxSize => {
+                "The pax header contains a record which may override the size. Readers without pax support locate the next header differently."
+            }
+            Issue::ReadOnlyDirectoryWithEntries => {
+                "Entries inside of a directory which is not writable by its owner cannot be extracted by unprivileged users if the directory is created first."
+            }
+            Issue::RegularDirectory => {
+                "A regular file with a trailing slash is extracted as directory by some implementations and as regular file by others."
+            }
+            Issue::TrailingByteNotNul => {
+                "Bytes after the end of archive marker should be nul. Other bytes indicate a corrupt or manipulated archive."
+            }
+            Issue::UnterminatedNumber => {
+                "Numeric fields must be terminated by a space or nul byte."
+            }
+            Issue::UnterminatedString => {
+                "String fields like uname and gname must be terminated by a nul byte."
+            }
+            Issue::UnusedByteNotNul => {
+                "Bytes after the terminator of a field should be nul. Other bytes leak data of the archiver or hide information."
+            }
+            Issue::PaxHeaderSizeOctal => {
+                "The length of a pax record is a decimal number. A leading zero may be interpreted as octal."
+            }
+            Issue::PaxHeaderSizeTooLarge => {
+                "The length of a pax record exceeds the extended header."
+            }
+            Issue::PaxHeaderSizeInvalid => {
+                "The length of a pax record is no decimal number or does not match the record."
+            }
+            Issue::PaxGlobalHeader => {
+                "Global pax headers apply to all following entries. Some implementations ignore them, others apply them only to the next entry."
+            }
+            Issue::PaxEmpty => "An extended header without records serves no purpose.",
+            Issue::PaxHeaderNoNewline => "Every pax record must end with a newline.",
+            Issue::PaxHeaderNoEqualSign => {
+                "Every pax record must separate keyword and value with an equal sign."
+            }
+            Issue::PaxHeaderKeywordBlank => {
+                "Blanks before the keyword are not allowed and are handled differently by implementations."
+            }
+            Issue::PaxHeaderKeywordEmpty => "Every pax record must have a keyword.",
+            Issue::PaxHeaderKeywordDuplicate => {
+                "If a keyword appears multiple times, some implementations use the first and others the last value."
+            }
+            Issue::PaxHeaderKeywordNoUtf8 => "Pax keywords must be encoded in UTF-8.",
+            Issue::PaxInvalidNumber => {
+                "The values of gid, uid, size, atime, ctime and mtime records are decimal numbers. Some implementations reject other values, others ignore them and use the ustar field."
+            }
+            Issue::PartialBlock => {
+                "Archives consist of 512 byte blocks. A truncated last block indicates a truncated archive."
+            }
+            Issue::ConcatenatedArchive => {
+                "Another archive follows the end of archive marker, e.g. created by cat or tar -A. GNU tar with --ignore-zeros extracts its entries, other implementations stop at the marker."
+            }
+            Issue::SymlinkAbsolute => {
+                "Absolute symbolic link targets point to files of the extracting system instead of files of the archive."
+            }
+            Issue::SymlinkEscapesRoot => {
+                "The symbolic link resolves to a location outside of the extraction directory. Entries written through it may overwrite arbitrary files."
+            }
+            Issue::SymlinkDangling => "The symbolic link target is not part of the archive.",
+            Issue::SymlinkCycle => "Resolving the symbolic link never ends and fails with ELOOP.",
+            Issue::HardLinkTargetMissing => {
+                "Hard links require their target to be extracted before. Extractors fail if the target is no preceding entry."
+            }
+            Issue::HardLinkToDirectory => {
+                "Hard links to directories cannot be created on most systems."
+            }
+            Issue::HardLinkToLink => {
+                "Hard links to links behave differently depending on whether the implementation follows the link."
+            }
+            Issue::HardLinkNotNormalized => {
+                "The hard link target only matches a preceding entry after normalization, e.g. by removing ./ or duplicate slashes. Implementations comparing paths literally fail."
+            }
+            Issue::HardLinkToSpecialFile => {
+                "Hard links to devices or fifos require the special file to be created, which needs privileges."
+            }
+            Issue::ParentNotDirectory => {
+                "A parent path component is a previous non-directory entry. Extractors either fail or replace the file with a directory."
+            }
+            Issue::DirectoryReplacesFile => {
+                "A directory entry has the same path as a previous non-directory entry. Extractors either fail or replace the file."
+            }
+            Issue::FileReplacesDirectory => {
+                "A non-directory entry has the same path as a previous directory. Extractors either fail or remove the directory with its contents."
+            }
+            Issue::MissingParentDirectory => {
+                "Extractors create missing parent directories with permissions depending on their umask, so the result differs between systems."
+            }
+            Issue::LateParentDirectory => {
+                "The directory entry follows its contents. Its permissions are applied after extracting the contents, which fails if the directory is read-only."
+            }
+            Issue::SetUidMode => {
+                "Files with setuid bit run with the privileges of their owner, which is rarely intended for extracted files."
+            }
+            Issue::SetGidMode => {
+                "Files with setgid bit run with the privileges of their group, directories pass their group on to new files."
+            }
+            Issue::StickyMode => {
+                "The sticky bit restricts deletion of files in directories, which is rarely intended for extracted files."
+            }
+            Issue::WorldWritableMode => "Every user of the extracting system can modify the entry.",
+            Issue::GroupWritableMode => {
+                "Every member of the group can modify the entry, which depends on the group database of the extracting system."
+            }
+            Issue::ExecutableMode => {
+                "The file is executable but starts neither with a script shebang nor with a known binary magic."
+            }
+            Issue::DirectoryNotSearchable => {
+                "Without execute permission, the owner cannot access files inside of the directory."
+            }
+            Issue::NonCanonicalMode => {
+                "Only 0755 for directories and executables and 0644 for other files are allowed. Other modes depend on the umask of the archiver."
+            }
+            Issue::NonRootOwner => {
+                "Extracting as root preserves ownership, so files belong to an arbitrary user of the extracting system. Archives should use uid and gid 0."
+            }
+            Issue::PersonalOwnerName => {
+                "User and group names of the archiver may reveal personal information."
+            }
+            Issue::LargeId => {
+                "Ids above 2097151 need more than 7 octal digits and are truncated by old implementations."
+            }
+            Issue::InconsistentOwner => {
+                "The owner differs from the owner of the first entry, which usually means files were copied from different users."
+            }
+            Issue::FutureTimestamp => {
+                "Extractors warn about modification times in the future and build tools may rebuild files endlessly."
+            }
+            Issue::ZeroTimestamp => {
+                "A modification time of zero usually means the time was lost. Build tools may consider such files outdated."
+            }
+            Issue::LargeTimestamp => {
+                "Modification times after the year 2242 do not fit into the 11 octal digits of ustar headers."
+            }
+            Issue::UnclampedTimestamp => {
+                "Reproducible builds clamp modification times to SOURCE_DATE_EPOCH. Newer times depend on the time of the build."
+            }
+            Issue::NonUniformTimestamp => {
+                "Reproducible archives use the same modification time for all entries. Differing times depend on the time of the build."
+            }
+            Issue::OwnerName => {
+                "Reproducible archives leave user and group names empty, because they depend on the build system."
+            }
+            Issue::PaxVolatileTime => {
+                "Access and change times depend on the time of the build and prevent reproducible archives."
+            }
+            Issue::PaxHeaderPid => {
+                "Pax header names containing the process id of the archiver differ between builds and prevent reproducible archives."
+            }
+            Issue::ExtendedHeaderTooLarge => {
+                "Extended headers and GNU long names are read into memory. Sizes above 1 MiB are far beyond any real path or record set and are not read."
+            }
+            Issue::ContiguousFile => {
+                "Contiguous files are reserved for high-performance storage. Most implementations extract them as regular files without any contiguity."
+            }
+            Issue::RuleViolation => {
+                "A custom rule added through the library reported a violation. The finding carries the code and message of the rule."
+            }
+            Issue::DuplicatePath => {
+                "Entries with the same path overwrite each other on extraction. Implementations disagree whether the first or the last entry wins, and some refuse to replace existing files."
+            }
+            Issue::UnsortedEntries => {
+                "The order of entries depends on the file system. Reproducible archives sort entries by path."
+            }
+            Issue::LargeDeviceNumber => {
+                "Linux supports device majors up to 4095 and minors up to 1048575. Larger numbers cannot be represented by extracting systems and are truncated or rejected."
+            }
+            Issue::SpecialFile => {
+                "Devices and fifos can only be created with privileges or are not supported by all systems and have no use in source archives."
+            }
+            Issue::GnuDumpDir => {
+                "The entry is a GNU incremental dump directory, which only GNU tar understands."
+            }
+            Issue::GnuLongLink => {
+                "GNU long link names are supported by GNU tar, bsdtar, busybox, Go and Python. Pax headers are the portable alternative."
+            }
+            Issue::GnuLongName => {
+                "GNU long names are supported by GNU tar, bsdtar, busybox, Go and Python. Pax headers are the portable alternative."
+            }
+            Issue::GnuMultiVolume => {
+                "The entry continues a file of a previous volume, which only GNU tar understands."
+            }
+            Issue::GnuOldLongNames => {
+                "The entry type for long names of old GNU tar versions is obsolete and not understood by current implementations."
+            }
+            Issue::GnuSparse => {
+                "Old GNU sparse files are supported by GNU tar, bsdtar, Go and Python. Other readers extract the compressed data as file content."
+            }
+            Issue::GnuVolumeLabel => {
+                "Volume labels are only understood by GNU tar and bsdtar, other readers extract them as files."
+            }
+            Issue::SolarisAcl => {
+                "Solaris ACL entries are only understood by Solaris tar and bsdtar."
+            }
+            Issue::SolarisExtendedAttribute => {
+                "Solaris extended attribute entries are only understood by Solaris tar."
+            }
+            Issue::SolarisExtendedHeader => {
+                "Solaris extended headers precede pax and are understood by Solaris tar, GNU tar, bsdtar and Python."
+            }
+            Issue::SolarisInode => {
+                "Inode only entries carry meta data without data, which only Solaris tar and star understand."
+            }
+            Issue::ImplementationDivergence => {
+                "Modelled implementations disagree about path, type or size of the entry, so the extracted result depends on the tool."
+            }
+            Issue::SignedChecksum => {
+                "The checksum is only valid if header bytes are summed as signed values, as done by historic Sun tar. Most readers accept it, but POSIX requires unsigned bytes."
+            }
+            Issue::UnusualChecksumFormat => {
+                "The checksum field has leading spaces or no terminator. Most readers accept it, but strict ones report a corrupt header."
+            }
+        }
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}] {}", self.code(), self.message())
+    }
+}
+
+pub fn eprint_issues(issues: &BTreeSet<Issue>) {
+    for issue in issues.iter() {
+        eprintln!("=> {}", issue);
     }
 }
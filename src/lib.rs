@@ -1,5 +1,13 @@
 pub mod archive;
+pub mod divergence;
+pub mod entry;
+pub mod error;
 pub mod header;
 pub mod issues;
+pub mod link;
 pub mod lint;
+pub mod listing;
+pub mod options;
 pub mod pax;
+pub mod policy;
+pub mod rule;
@@ -0,0 +1,222 @@
+use crate::header::normalize;
+use crate::issues::Issue;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::VecDeque;
+
+/// Maximum amount of symbolic links followed, just like ELOOP on Linux.
+const MAX_SYMLINK_HOPS: usize = 40;
+
+pub struct LinkReport {
+    pub issue: Issue,
+    /// Paths encountered during resolution, starting with the link itself.
+    /// Paths of archive entries are accompanied by their block offset.
+    pub chain: Vec<(String, Option<usize>)>,
+}
+
+pub struct Symlink {
+    pub linkname: String,
+    pub offset: usize,
+}
+
+/// Checks that hard link target is a preceding regular file.
+///
+/// Linkname is compared as stored in the archive with the stored names of
+/// entries, because extractors look up targets by their stored names.
+/// Entries are known paths with block offset, names their stored names
+/// and typeflags their types.
+pub fn check_hardlink(
+    path: &str,
+    offset: usize,
+    linkname: &str,
+    entries: &BTreeMap<String, usize>,
+    names: &BTreeMap<String, String>,
+    typeflags: &BTreeMap<String, u8>,
+) -> Option<LinkReport> {
+    let mut target = normalize(linkname.to_string());
+    if target.ends_with('/') {
+        target.pop();
+    }
+    let mut chain = vec![(path.to_string(), Some(offset))];
+    let (Some(&target_offset), Some(&typeflag)) = (entries.get(&target), typeflags.get(&target))
+    else {
+        chain.push((linkname.to_string(), None));
+        return Some(LinkReport {
+            issue: Issue::HardLinkTargetMissing,
+            chain,
+        });
+    };
+    chain.push((linkname.to_string(), Some(target_offset)));
+    let issue = match typeflag {
+        b'0' | b'7' => {
+            if names.get(&target).is_some_and(|n| n == linkname) {
+                return None;
+            }
+            Issue::HardLinkNotNormalized
+        }
+        b'1' | b'2' => Issue::HardLinkToLink,
+        b'5' => Issue::HardLinkToDirectory,
+        _ => Issue::HardLinkToSpecialFile,
+    };
+    Some(LinkReport { issue, chain })
+}
+
+/// Resolves symbolic link at path through the symbolic links of archive.
+///
+/// Paths are expected to be normalized without trailing slash. Entries
+/// are known paths with block offset.
+pub fn resolve_symlink(
+    path: &str,
+    symlinks: &BTreeMap<String, Symlink>,
+    entries: &BTreeMap<String, usize>,
+) -> Option<LinkReport> {
+    let link = &symlinks[path];
+    let mut chain = vec![(path.to_string(), Some(link.offset))];
+    if link.linkname.starts_with('/') {
+        chain.push((link.linkname.clone(), None));
+        return Some(LinkReport {
+            issue: Issue::SymlinkAbsolute,
+            chain,
+        });
+    }
+    let mut resolved: Vec<&str> = path.split('/').collect();
+    resolved.pop();
+    let mut pending: VecDeque<&str> = link.linkname.split('/').collect();
+    let mut states = BTreeSet::new();
+    states.insert((path.split('/').collect(), VecDeque::new()));
+    while let Some(component) = pending.pop_front() {
+        match component {
+            "" | "." => continue,
+            ".." => {
+                if resolved.pop().is_none() {
+                    chain.push((link.linkname.clone(), None));
+                    return Some(LinkReport {
+                        issue: Issue::SymlinkEscapesRoot,
+                        chain,
+                    });
+                }
+                continue;
+            }
+            _ => resolved.push(component),
+        }
+        let current = resolved.join("/");
+        if let Some(s) = symlinks.get(&current) {
+            chain.push((current, Some(s.offset)));
+            if !states.insert((resolved.clone(), pending.clone())) || chain.len() > MAX_SYMLINK_HOPS
+            {
+                return Some(LinkReport {
+                    issue: Issue::SymlinkCycle,
+                    chain,
+                });
+            }
+            if s.linkname.starts_with('/') {
+                chain.push((s.linkname.clone(), None));
+                return Some(LinkReport {
+                    issue: Issue::SymlinkAbsolute,
+                    chain,
+                });
+            }
+            resolved.pop();
+            for c in s.linkname.split('/').rev() {
+                pending.push_front(c);
+            }
+        }
+    }
+    let target = resolved.join("/");
+    if target.is_empty() {
+        return None;
+    }
+    if entries.contains_key(&target) {
+        return None;
+    }
+    let mut dir = target.clone();
+    dir.push('/');
+    if entries
+        .range(dir.clone()..)
+        .next()
+        .is_some_and(|(p, _)| p.starts_with(&dir))
+    {
+        return None;
+    }
+    chain.push((target, None));
+    Some(LinkReport {
+        issue: Issue::SymlinkDangling,
+        chain,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Symlink;
+    use super::resolve_symlink;
+    use crate::issues::Issue;
+    use std::collections::BTreeMap;
+
+    fn archive(
+        links: &[(&str, &str)],
+        files: &[&str],
+    ) -> (BTreeMap<String, Symlink>, BTreeMap<String, usize>) {
+        let mut symlinks = BTreeMap::new();
+        let mut entries = BTreeMap::new();
+        for (n, (path, linkname)) in links.iter().enumerate() {
+            let symlink = Symlink {
+                linkname: linkname.to_string(),
+                offset: n,
+            };
+            symlinks.insert(path.to_string(), symlink);
+            entries.insert(path.to_string(), n);
+        }
+        for (n, path) in files.iter().enumerate() {
+            entries.insert(path.to_string(), links.len() + n);
+        }
+        (symlinks, entries)
+    }
+
+    fn issue(path: &str, links: &[(&str, &str)], files: &[&str]) -> Option<Issue> {
+        let (symlinks, entries) = archive(links, files);
+        resolve_symlink(path, &symlinks, &entries).map(|r| r.issue)
+    }
+
+    #[test]
+    fn test_resolve_symlink() {
+        assert_eq!(None, issue("d/l", &[("d/l", "../f")], &["f"]));
+        assert_eq!(None, issue("d/l", &[("d/l", "../e")], &["e/f"]));
+        assert_eq!(None, issue("l", &[("l", "m"), ("m", "f")], &["f"]));
+        assert_eq!(None, issue("l", &[("l", ".")], &[]));
+        assert_eq!(
+            Some(Issue::SymlinkAbsolute),
+            issue("l", &[("l", "/etc/passwd")], &[])
+        );
+        assert_eq!(
+            Some(Issue::SymlinkAbsolute),
+            issue("l", &[("l", "m"), ("m", "/etc")], &[])
+        );
+        assert_eq!(
+            Some(Issue::SymlinkEscapesRoot),
+            issue("d/l", &[("d/l", "../../f")], &[])
+        );
+        assert_eq!(
+            Some(Issue::SymlinkEscapesRoot),
+            issue("d/l", &[("d/l", "../m/../.."), ("m", "d")], &[])
+        );
+        assert_eq!(
+            Some(Issue::SymlinkDangling),
+            issue("l", &[("l", "f")], &["g"])
+        );
+        assert_eq!(
+            Some(Issue::SymlinkCycle),
+            issue("l", &[("l", "m"), ("m", "l")], &[])
+        );
+        assert_eq!(Some(Issue::SymlinkCycle), issue("l", &[("l", "l/x")], &[]));
+    }
+
+    #[test]
+    fn test_resolve_symlink_chain() {
+        let (symlinks, entries) = archive(&[("d/l", "m"), ("d/m", "../f")], &["f"]);
+        assert!(resolve_symlink("d/l", &symlinks, &entries).is_none());
+        let (symlinks, entries) = archive(&[("l", "m"), ("m", "l")], &[]);
+        let report = resolve_symlink("l", &symlinks, &entries).unwrap();
+        let chain: Vec<&str> = report.chain.iter().map(|(p, _)| p.as_str()).collect();
+        assert_eq!(vec!["l", "m", "l"], chain);
+    }
+}
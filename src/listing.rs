@@ -0,0 +1,159 @@
+use crate::entry::Entry;
+use crate::header::BLOCK_SIZE;
+use crate::header::LintHeader;
+use crate::issues::Finding;
+use crate::lint::lint_number_field;
+use crate::lint::lint_string_field;
+
+/// Width of owner and size columns, as used by GNU tar.
+const OWNER_SIZE_WIDTH: usize = 19;
+
+/// Returns type and permissions of an entry like `ls -l`, e.g. "drwxr-xr-x".
+pub fn mode_string(typeflag: u8, mode: u64) -> String {
+    let kind = match typeflag {
+        b'0' | 0 | b'S' | b'M' => '-',
+        b'1' => 'h',
+        b'2' => 'l',
+        b'3' => 'c',
+        b'4' => 'b',
+        b'5' | b'D' => 'd',
+        b'6' => 'p',
+        b'7' => 'C',
+        t if t.is_ascii_graphic() => t as char,
+        _ => '?',
+    };
+    let mut s = String::from(kind);
+    for shift in [6, 3, 0] {
+        let bits = mode >> shift;
+        s.push(if bits & 4 != 0 { 'r' } else { '-' });
+        s.push(if bits & 2 != 0 { 'w' } else { '-' });
+        let special = match shift {
+            6 => mode & 0o4000 != 0,
+            3 => mode & 0o2000 != 0,
+            _ => mode & 0o1000 != 0,
+        };
+        let (set, unset) = if shift == 0 { ('t', 'T') } else { ('s', 'S') };
+        s.push(match (bits & 1 != 0, special) {
+            (true, true) => set,
+            (false, true) => unset,
+            (true, false) => 'x',
+            (false, false) => '-',
+        });
+    }
+    s
+}
+
+/// Formats seconds since epoch as UTC date and time, e.g. "2024-01-31 12:00".
+pub fn format_time(seconds: u64) -> String {
+    /* days to civil date, see Howard Hinnant's chrono-compatible algorithms */
+    let days = seconds / 86400;
+    let z = days + 719468;
+    let era = z / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    let time = seconds % 86400;
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60
+    )
+}
+
+/// Returns a line describing an entry like `tar -tv`, using effective
+/// values after pax records and GNU long names are applied.
+pub fn list_entry(header: &LintHeader) -> String {
+    let user = if header.uname.is_empty() {
+        header.uid.to_string()
+    } else {
+        header.uname.clone()
+    };
+    let group = if header.gname.is_empty() {
+        header.gid.to_string()
+    } else {
+        header.gname.clone()
+    };
+    let owner = format!("{}/{}", user, group);
+    let size = if header.typeflag == b'3' || header.typeflag == b'4' {
+        format!("{},{}", header.devmajor, header.devminor)
+    } else {
+        header.size.to_string()
+    };
+    let width = OWNER_SIZE_WIDTH.saturating_sub(owner.len() + 1);
+    let mut line = format!(
+        "{} {} {:>width$} {} {}",
+        mode_string(header.typeflag, header.mode),
+        owner,
+        size,
+        format_time(header.mtime),
+        header.path,
+        width = width
+    );
+    match header.typeflag {
+        b'1' => line.push_str(&format!(" link to {}", header.linkname)),
+        b'2' => line.push_str(&format!(" -> {}", header.linkname)),
+        _ => {}
+    }
+    line
+}
+
+/// Decodes a header field by name the way alquitran interprets it, None
+/// for fields without meaningful value. Times are shown as UTC date.
+pub fn decode_field(name: &str, bytes: &[u8]) -> Option<String> {
+    let empty = bytes.iter().all(|&b| b == 0);
+    let value = match name {
+        /* old GNU headers leave device numbers and their own fields empty */
+        "devmajor" | "devminor" | "atime" | "ctime" | "offset" | "realsize" if empty => {
+            return None;
+        }
+        "mode" => lint_number_field(bytes).value.map(|n| format!("{:04o}", n)),
+        "uid" | "gid" | "size" | "chksum" | "devmajor" | "devminor" | "offset" | "realsize" => {
+            lint_number_field(bytes).value.map(|n| n.to_string())
+        }
+        "mtime" | "atime" | "ctime" => lint_number_field(bytes)
+            .value
+            .map(|n| format!("{} ({})", n, format_time(n))),
+        "typeflag" | "magic" | "version" => {
+            let used = bytes.iter().rposition(|&b| b != 0).map_or(0, |n| n + 1);
+            Some(format!("'{}'", bytes[..used].escape_ascii()))
+        }
+        "isextended" => Some(bytes[0].to_string()),
+        "longnames" | "unused" | "sparse" | "padding" => return None,
+        _ => lint_string_field(bytes)
+            .value
+            .map(|v| format!("'{}'", String::from_utf8_lossy(&v))),
+    };
+    Some(value.unwrap_or_else(|| "invalid".to_string()))
+}
+
+/// Observer of entries while an archive is linted, e.g. to list or dump
+/// them along with their findings in a single pass.
+///
+/// In contrast to rules, visitors are informed about extended headers
+/// and about entries of concatenated archives.
+pub trait Visitor {
+    /// Called before data of an entry is read, unless a fatal issue was
+    /// found in its header. Returns whether its data blocks are wanted.
+    fn entry(&mut self, _entry: &Entry) -> bool {
+        false
+    }
+
+    /// Receives a data block of the current entry and its block offset.
+    /// The last block includes its padding.
+    fn block(&mut self, _offset: usize, _block: &[u8; BLOCK_SIZE]) {}
+
+    /// Called once all checks of an entry are done with the findings
+    /// concerning it.
+    fn checked(&mut self, _entry: &Entry, _findings: &[Finding]) {}
+
+    /// Called after the last entry of an archive with the findings of
+    /// symbolic links, which are resolved once all entries are known.
+    fn resolved(&mut self, _findings: &[Finding]) {}
+}
@@ -1,36 +1,173 @@
 use alquitran::archive::Archive;
+use alquitran::archive::ArchiveLintResult;
+use alquitran::archive::BLOCKING_FACTORS;
+use alquitran::archive::Dump;
+use alquitran::archive::EXIT_ERRORS;
+use alquitran::entry::Entry;
+use alquitran::error::Error;
 use alquitran::header::BLOCK_SIZE;
 use alquitran::header::Format;
+use alquitran::header::fields;
+use alquitran::issues::Finding;
+use alquitran::issues::Issue;
+use alquitran::issues::eprint_hints;
 use alquitran::issues::eprint_issues;
+use alquitran::lint::ERROR;
+use alquitran::lint::WARNING;
+use alquitran::listing::Visitor;
+use alquitran::listing::decode_field;
+use alquitran::listing::list_entry;
+use alquitran::options::Options;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::env;
+use std::error;
 use std::fs::File;
 use std::io;
 use std::io::BufReader;
-use std::io::Result;
+use std::io::IsTerminal;
+use std::io::Read;
 use std::process::exit;
+use std::rc::Rc;
 
-fn main() -> Result<()> {
-    let args: Vec<String> = env::args().collect();
-    if args.len() > 2 {
-        eprintln!("usage: alquitran [file.tar]");
-        exit(1);
+/// Entry or block selected for inspection.
+enum Target {
+    Block(usize),
+    Entry(usize),
+    Path(String),
+}
+
+/// Invalid command line arguments, like EX_USAGE of sysexits.h.
+const EXIT_USAGE: i32 = 64;
+/// Archive could not be read, like EX_IOERR of sysexits.h.
+const EXIT_IO: i32 = 74;
+
+type Result<T> = std::result::Result<T, Box<dyn error::Error>>;
+
+fn main() {
+    match run() {
+        Ok(code) => exit(code),
+        Err(e) => {
+            eprint!("alquitran: {}", e);
+            let mut source = e.source();
+            while let Some(s) = source {
+                eprint!(": {}", s);
+                source = s.source();
+            }
+            eprintln!();
+            /* truncated archives are malformed */
+            if let Some(Error::Truncated { .. }) = e.downcast_ref::<Error>() {
+                exit(EXIT_ERRORS);
+            }
+            exit(EXIT_IO);
+        }
     }
-    let mut archive = if args.len() < 2 {
-        Archive::new(Box::new(io::stdin()))
-    } else {
-        let file = File::open(&args[1])?;
-        let reader = BufReader::with_capacity(512, file);
-        Archive::new(Box::new(reader))
-    };
-    let result = archive.lint()?;
+}
 
-    if let Some(d) = &result.dump {
-        eprint_bytes(&d.bytes, &d.marks, d.offset);
+fn run() -> Result<i32> {
+    let mut options = Options::default();
+    let mut file = None;
+    let mut explain_header = false;
+    let mut color = None;
+    let mut data = false;
+    let mut target = None;
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let command = take_command(&mut args);
+    let mut args = args.into_iter();
+    let inspect = command.as_deref() == Some("inspect");
+    let list = command.as_deref() == Some("list");
+    while let Some(arg) = args.next() {
+        if let Some(v) = arg.strip_prefix("--color=") {
+            color = parse_color(v);
+            continue;
+        }
+        if let Some(v) = arg.strip_prefix("--source-date-epoch=") {
+            options.source_date_epoch = Some(parse_epoch(v));
+            continue;
+        }
+        match arg.as_str() {
+            "--anonymous-names" => options.anonymous_names = true,
+            "--block" if inspect => target = Some(Target::Block(parse_index(args.next()))),
+            "--canonical-modes" => options.canonical_modes = true,
+            "--color" => color = Some(true),
+            "--consistent-owners" => options.consistent_owners = true,
+            "--data" if inspect => data = true,
+            "--entry" if inspect => target = Some(Target::Entry(parse_index(args.next()))),
+            "--executable-modes" => options.executable_modes = true,
+            "--explain" => match args.next() {
+                Some(code) => explain(&code),
+                None => usage(),
+            },
+            "--explain-header" => explain_header = true,
+            "--large-ids" => options.large_ids = true,
+            "--modes" => {
+                options.special_mode_bits = true;
+                options.writable_modes = true;
+                options.executable_modes = true;
+                options.searchable_directories = true;
+            }
+            "--numeric-owner" => options.numeric_owner = true,
+            "--owners" => {
+                options.numeric_owner = true;
+                options.anonymous_names = true;
+                options.consistent_owners = true;
+                options.large_ids = true;
+            }
+            "--parent-directories" => options.parent_directories = true,
+            "--path" if inspect => match args.next() {
+                Some(path) => target = Some(Target::Path(path)),
+                None => usage(),
+            },
+            "--reproducible" => options.reproducible(),
+            "--searchable-directories" => options.searchable_directories = true,
+            "--source-date-epoch" => match env::var("SOURCE_DATE_EPOCH") {
+                Ok(v) => options.source_date_epoch = Some(parse_epoch(&v)),
+                Err(_) => {
+                    eprintln!("SOURCE_DATE_EPOCH is not set");
+                    usage()
+                }
+            },
+            "--special-files" => options.special_files = true,
+            "--special-mode-bits" => options.special_mode_bits = true,
+            "--timestamps" => options.timestamps = true,
+            "--writable-modes" => options.writable_modes = true,
+            _ if arg.starts_with('-') || file.is_some() => usage(),
+            _ => file = Some(arg),
+        }
     }
-    eprint_issues(&result.issues);
-    for path in result.duplicated_paths.iter() {
-        eprintln!("=> Multiple entries for path '{}'.", path);
+    /*
+     * NO_COLOR disables colours unless they are explicitly requested,
+     * see https://no-color.org/
+     */
+    let color = color.unwrap_or_else(|| {
+        let terminal = if inspect {
+            io::stdout().is_terminal()
+        } else {
+            io::stderr().is_terminal()
+        };
+        env::var_os("NO_COLOR").is_none_or(|v| v.is_empty()) && terminal
+    });
+    let reader: Box<dyn Read> = match file {
+        None => Box::new(io::stdin()),
+        Some(f) => {
+            let file = File::open(f)?;
+            Box::new(BufReader::with_capacity(512, file))
+        }
+    };
+    if inspect {
+        match target {
+            Some(t) => return inspect_archive(reader, options, t, data, color).map(|_| 0),
+            None => usage(),
+        }
+    }
+    if list {
+        let result = list_entries(reader, options)?;
+        return Ok(result.exit_code());
     }
+    let mut archive = Archive::with_options(reader, options);
+    let result = archive.lint()?;
+    eprint_result(&result, explain_header, color);
 
     let format = match result.format {
         Some(f) => match f {
@@ -42,48 +179,575 @@ fn main() -> Result<()> {
         None => "unknown",
     };
     println!("Detected format: {}", format);
+    if let Some(r) = &result.records {
+        println!("Archive size: {} bytes", r.length);
+        match r.blocking_factor() {
+            Some(n) => println!(
+                "Blocking factor: {} ({} bytes per record)",
+                n,
+                n * BLOCK_SIZE as u64
+            ),
+            None => println!("Blocking factor: none"),
+        }
+        let sizes: Vec<String> = BLOCKING_FACTORS
+            .iter()
+            .filter(|&&n| r.is_multiple_of(n))
+            .map(|n| (n * BLOCK_SIZE as u64).to_string())
+            .collect();
+        if !sizes.is_empty() {
+            println!("Multiple of record sizes: {}", sizes.join(", "));
+        }
+        println!("Padding after end of archive: {} bytes", r.padding);
+    }
+    match &result.order.unsorted {
+        Some((a, b)) => println!("Entry order: unsorted ('{}' before '{}')", a, b),
+        None => println!("Entry order: sorted"),
+    }
+    match &result.order.misplaced {
+        Some((a, b)) => println!("Directory order: contents first ('{}' before '{}')", a, b),
+        None => println!("Directory order: directories first"),
+    }
 
     if result.is_portable() {
         println!("No issues found.");
+    }
+    Ok(result.exit_code())
+}
+
+/// Prints entries like `tar -tv` with the byte offsets of their headers
+/// and the codes of their findings while the archive is linted.
+struct Lister;
+
+impl Visitor for Lister {
+    fn checked(&mut self, entry: &Entry, findings: &[Finding]) {
+        let mut line = format!(
+            "{:08x} {}",
+            entry.offset * BLOCK_SIZE,
+            list_entry(&entry.header)
+        );
+        let codes: BTreeSet<String> = findings.iter().map(|f| f.code()).collect();
+        if !codes.is_empty() {
+            let codes: Vec<&str> = codes.iter().map(|c| c.as_str()).collect();
+            line.push_str(&format!(" [{}]", codes.join(", ")));
+        }
+        println!("{}", line);
+    }
+
+    fn resolved(&mut self, findings: &[Finding]) {
+        for finding in findings {
+            println!(
+                "{:08x} {} [{}]",
+                finding.offset * BLOCK_SIZE,
+                finding.path,
+                finding.code()
+            );
+        }
+    }
+}
+
+/// Lists entries of an archive including those of a concatenated archive.
+fn list_entries(reader: Box<dyn Read>, options: Options) -> Result<ArchiveLintResult> {
+    Ok(Archive::with_options(reader, options).visit(&mut Lister)?)
+}
+
+/// Prints header, pax records, optionally data blocks and findings of
+/// the selected entry while the archive is linted.
+struct Inspector {
+    target: Target,
+    data: bool,
+    color: bool,
+    /// Index of the current entry within its archive.
+    index: usize,
+    /// First data block and data length of the entry being dumped.
+    dumping: Option<(usize, u64)>,
+    /// Header of the selected entry was printed.
+    found: bool,
+    /// All findings of the selected entry were printed.
+    finished: bool,
+}
+
+impl Inspector {
+    fn selects(&self, entry: &Entry) -> bool {
+        !self.found
+            && match &self.target {
+                Target::Block(n) => entry.offset == *n,
+                Target::Entry(n) => self.index == *n,
+                Target::Path(p) => {
+                    entry.header.path.trim_end_matches('/') == p.trim_end_matches('/')
+                }
+            }
+    }
+
+    fn print_header(&mut self, entry: &Entry) {
+        self.found = true;
+        println!(
+            "Entry #{} '{}' at {:08x}:",
+            self.index,
+            entry.header.path,
+            entry.offset * BLOCK_SIZE
+        );
+        print!(
+            "{}",
+            format_bytes(
+                &entry.header.bytes,
+                &entry.header.marks,
+                entry.offset,
+                self.color
+            )
+        );
+        if let Some(p) = &entry.extended {
+            print_records("Pax records", &p.records);
+        }
+        print_records("Applied pax records", &entry.header.pax);
+    }
+}
+
+impl Visitor for Inspector {
+    fn entry(&mut self, entry: &Entry) -> bool {
+        if !self.selects(entry) {
+            return false;
+        }
+        self.print_header(entry);
+        if !self.data || entry.is_empty() {
+            return false;
+        }
+        println!("Data ({} bytes):", entry.len());
+        self.dumping = Some((entry.data_offset(), entry.len()));
+        true
+    }
+
+    fn block(&mut self, offset: usize, block: &[u8; BLOCK_SIZE]) {
+        let Some((first, length)) = self.dumping else {
+            return;
+        };
+        let mut marks = [0; BLOCK_SIZE];
+        let last = first + length.div_ceil(BLOCK_SIZE as u64) as usize - 1;
+        let used = (length % BLOCK_SIZE as u64) as usize;
+        if offset == last && used != 0 {
+            for pos in used..BLOCK_SIZE {
+                if block[pos] != 0 {
+                    marks[pos] |= ERROR;
+                }
+            }
+        }
+        print!("{}", format_bytes(block, &marks, offset, self.color));
+    }
+
+    fn checked(&mut self, entry: &Entry, findings: &[Finding]) {
+        if self.selects(entry) {
+            self.print_header(entry);
+        }
+        if self.found && !self.finished {
+            for finding in findings {
+                println!("{}", format_finding(finding));
+            }
+            self.dumping = None;
+            self.finished = true;
+        }
+        self.index += 1;
+    }
+
+    fn resolved(&mut self, _findings: &[Finding]) {
+        self.index = 0;
+    }
+}
+
+/// Reader which keeps a copy of one block while passing bytes through.
+/// Clones share their state, so reading can continue after linting
+/// stopped.
+#[derive(Clone)]
+struct Capture(Rc<RefCell<Captured>>);
+
+struct Captured {
+    reader: Box<dyn Read>,
+    /// Amount of bytes read so far.
+    position: u64,
+    block: usize,
+    bytes: Vec<u8>,
+}
+
+impl Read for Capture {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut captured = self.0.borrow_mut();
+        let c = &mut *captured;
+        let n = c.reader.read(buf)?;
+        let start = (c.block * BLOCK_SIZE) as u64;
+        let from = c.position.max(start);
+        let to = (c.position + n as u64).min(start + BLOCK_SIZE as u64);
+        if from < to {
+            let range = (from - c.position) as usize..(to - c.position) as usize;
+            c.bytes.extend_from_slice(&buf[range]);
+        }
+        c.position += n as u64;
+        Ok(n)
+    }
+}
+
+/// Inspects selected entry, or prints the raw block if no header starts
+/// at selected block, even if linting stopped before it.
+fn inspect_archive(
+    reader: Box<dyn Read>,
+    options: Options,
+    target: Target,
+    data: bool,
+    color: bool,
+) -> Result<()> {
+    let (reader, capture): (Box<dyn Read>, _) = match target {
+        Target::Block(n) => {
+            let captured = Captured {
+                reader,
+                position: 0,
+                block: n,
+                bytes: Vec::new(),
+            };
+            let capture = Capture(Rc::new(RefCell::new(captured)));
+            (Box::new(capture.clone()), Some(capture))
+        }
+        _ => (reader, None),
+    };
+    let mut inspector = Inspector {
+        target,
+        data,
+        color,
+        index: 0,
+        dumping: None,
+        found: false,
+        finished: false,
+    };
+    let result = Archive::with_options(reader, options).visit(&mut inspector);
+    /* later entries do not concern the selected one */
+    if inspector.finished {
         return Ok(());
     }
-    exit(1);
+    if !inspector.found
+        && let Some(mut capture) = capture
+    {
+        let (n, position) = {
+            let c = capture.0.borrow();
+            (c.block, c.position)
+        };
+        let end = ((n + 1) * BLOCK_SIZE) as u64;
+        if position < end {
+            io::copy(&mut capture.by_ref().take(end - position), &mut io::sink())?;
+        }
+        let c = capture.0.borrow();
+        if c.bytes.len() == BLOCK_SIZE {
+            println!("Block {}:", n);
+            print!("{}", format_bytes(&c.bytes, &[0; BLOCK_SIZE], n, color));
+            return Ok(());
+        }
+    }
+    result?;
+    eprintln!("alquitran: no such entry or block");
+    exit(EXIT_USAGE);
+}
+
+fn print_records(title: &str, records: &BTreeMap<String, Vec<u8>>) {
+    if records.is_empty() {
+        return;
+    }
+    println!("{}:", title);
+    for (keyword, value) in records.iter() {
+        println!("  {}={}", keyword, value.escape_ascii());
+    }
+}
+
+fn explain(code: &str) -> ! {
+    let Some(issue) = Issue::from_code(&code.to_uppercase()) else {
+        eprintln!("alquitran: unknown issue code '{}'", code);
+        exit(EXIT_USAGE);
+    };
+    println!("{}: {}", issue.code(), issue.message());
+    println!("Severity: {}", issue.severity());
+    println!("Category: {}", issue.category());
+    if let Some(reference) = issue.reference() {
+        println!("Reference: {}", reference);
+    }
+    println!();
+    println!("{}", issue.explanation());
+    exit(0);
+}
+
+/// Removes the first command from arguments, skipping values of options.
+fn take_command(args: &mut Vec<String>) -> Option<String> {
+    let mut n = 0;
+    while n < args.len() {
+        match args[n].as_str() {
+            "list" | "inspect" => return Some(args.remove(n)),
+            "--block" | "--entry" | "--explain" | "--path" => n += 2,
+            _ => n += 1,
+        }
+    }
+    None
+}
+
+fn parse_epoch(value: &str) -> u64 {
+    match value.parse() {
+        Ok(n) => n,
+        Err(_) => usage(),
+    }
+}
+
+/// Returns None for auto detection.
+fn parse_color(value: &str) -> Option<bool> {
+    match value {
+        "always" => Some(true),
+        "auto" => None,
+        "never" => Some(false),
+        _ => usage(),
+    }
+}
+
+fn parse_index(value: Option<String>) -> usize {
+    match value.map(|v| v.parse()) {
+        Some(Ok(n)) => n,
+        _ => usage(),
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("usage: alquitran [options] [file.tar]");
+    eprintln!("       alquitran list [options] [file.tar]");
+    eprintln!("       alquitran inspect [--data] --block n|--entry n|--path path [file.tar]");
+    eprintln!("       alquitran --explain code");
+    exit(EXIT_USAGE);
+}
+
+fn eprint_result(result: &ArchiveLintResult, explain_header: bool, color: bool) {
+    if explain_header && !result.dumps.is_empty() {
+        if color {
+            eprintln!(
+                "Legend: {} bytes have warnings, {} bytes have errors.",
+                colored("yellow", WARNING, color),
+                colored("red", ERROR, color)
+            );
+        } else {
+            eprintln!(
+                "Legend: bytes with warnings are marked {}, bytes with errors are marked {}.",
+                marker(WARNING),
+                marker(ERROR)
+            );
+        }
+    }
+    for d in result.dumps.iter() {
+        let block = d.offset * BLOCK_SIZE..(d.offset + 1) * BLOCK_SIZE;
+        let findings: Vec<&Finding> = result
+            .findings
+            .iter()
+            .filter(|f| block.contains(&f.location.start))
+            .collect();
+        /* data blocks have no fields to annotate */
+        if explain_header && findings.iter().all(|f| f.field.is_some()) {
+            eprint_fields(d, &findings, color);
+            continue;
+        }
+        eprint_bytes(&d.bytes, &d.marks, d.offset, color);
+        for finding in findings {
+            eprint_finding(finding);
+        }
+    }
+    /* custom rules may report findings without marked bytes */
+    for finding in result.findings.iter() {
+        let block = finding.location.start / BLOCK_SIZE;
+        if !result.dumps.iter().any(|d| d.offset == block) {
+            eprint_finding(finding);
+        }
+    }
+    eprint_hints(&result.hints);
+    eprint_issues(&result.issues);
+    for path in result.duplicated_paths.iter() {
+        eprintln!("=> Multiple entries for path '{}'.", path);
+    }
+    for path in result.missing_directories.iter() {
+        eprintln!("=> Missing directory entry for '{}'.", path);
+    }
+    for path in result.late_directories.iter() {
+        eprintln!("=> Directory entry '{}' follows its contents.", path);
+    }
+    for link in result.links.iter() {
+        let chain: Vec<String> = link
+            .chain
+            .iter()
+            .map(|(path, offset)| match offset {
+                Some(o) => format!("'{}' ({:08x})", path, o * BLOCK_SIZE),
+                None => format!("'{}'", path),
+            })
+            .collect();
+        eprintln!("=> Link resolution: {}", chain.join(" -> "));
+    }
+    for divergence in result.divergences.iter() {
+        eprintln!(
+            "=> Implementations disagree about entry at offset {:08x}:",
+            divergence.offset * BLOCK_SIZE
+        );
+        for (implementation, interpretation) in divergence.interpretations.iter() {
+            match interpretation {
+                Some(i) => eprintln!(
+                    "   {}: '{}', type '{}', {} bytes",
+                    implementation.name(),
+                    i.path,
+                    i.typeflag.escape_ascii(),
+                    i.size
+                ),
+                None => eprintln!("   {}: stops reading", implementation.name()),
+            }
+        }
+    }
+    if let Some(c) = &result.concatenated {
+        eprintln!(
+            "=> Concatenated archive at offset {:08x}:",
+            c.offset * BLOCK_SIZE
+        );
+        for path in c.entries.iter() {
+            eprintln!("=> Hidden entry '{}'.", path);
+        }
+        if let Some(e) = &c.error {
+            eprintln!("=> Concatenated archive stops reading: {}", e);
+        }
+        eprint_result(c, explain_header, color);
+    }
+}
+
+fn eprint_finding(finding: &Finding) {
+    eprintln!("{}", format_finding(finding));
+}
+
+fn format_finding(finding: &Finding) -> String {
+    let field = match finding.field {
+        Some(name) => format!("{} field", name),
+        None => "data".to_string(),
+    };
+    /* archive-wide violations of custom rules */
+    if finding.rule.is_some() && finding.path.is_empty() {
+        return format!("=> {}", finding);
+    }
+    let location = &finding.location;
+    let bytes = if location.is_empty() {
+        format!("byte {:#010x}", location.start)
+    } else {
+        format!("bytes {:#010x}-{:#010x}", location.start, location.end - 1)
+    };
+    format!(
+        "=> Entry '{}' #{}: {} at {}: {}",
+        finding.path, finding.entry, field, bytes, finding
+    )
 }
 
-fn eprint_bytes(bytes: &[u8], marks: &[u8], offset: usize) {
+/// Prints every header field with raw bytes, decoded value and findings.
+fn eprint_fields(dump: &Dump, findings: &[&Finding], color: bool) {
+    let start = dump.offset * BLOCK_SIZE;
+    eprintln!("Header at {:08x}:", start);
+    for (name, range) in fields(&dump.bytes).iter() {
+        let bytes = &dump.bytes[range.clone()];
+        let prefix = format!(
+            "  {:<10} {:08x}-{:08x}  ",
+            name,
+            start + range.start,
+            start + range.end - 1
+        );
+        let mut raw = String::new();
+        let mut markers = String::new();
+        /* trailing nul bytes are shortened to a count */
+        let used = bytes.iter().rposition(|&b| b != 0).map_or(0, |n| n + 1);
+        for n in range.start..range.start + used {
+            let text = escape(dump.bytes[n]);
+            markers.push_str(&marker(dump.marks[n]).repeat(text.len()));
+            raw.push_str(&colored(&text, dump.marks[n], color));
+        }
+        let nul = bytes.len() - used;
+        if nul > 0 {
+            let mark = dump.marks[range.start + used..range.end]
+                .iter()
+                .fold(0, |a, &m| a | m);
+            let text = if nul == 1 {
+                escape(0)
+            } else {
+                format!("{}*{}", escape(0), nul)
+            };
+            markers.push_str(&marker(mark).repeat(text.len()));
+            raw.push_str(&colored(&text, mark, color));
+        }
+        match decode_field(name, bytes) {
+            Some(value) => eprintln!("{}{} => {}", prefix, raw, value),
+            None => eprintln!("{}{}", prefix, raw),
+        }
+        if !color && !markers.trim_end().is_empty() {
+            eprintln!("{:width$}{}", "", markers.trim_end(), width = prefix.len());
+        }
+        for finding in findings.iter() {
+            if range.contains(&(finding.location.start - start)) {
+                eprintln!("     {}", finding);
+            }
+        }
+    }
+}
+
+fn escape(b: u8) -> String {
+    match b {
+        0 => "\\0".to_string(),
+        _ => [b].escape_ascii().to_string(),
+    }
+}
+
+fn colored(text: &str, mark: u8, color: bool) -> String {
+    match mark {
+        _ if !color => text.to_string(),
+        0 => text.to_string(),
+        WARNING => format!("\x1b[0;33m{}\x1b[0m", text),
+        _ => format!("\x1b[0;31m{}\x1b[0m", text),
+    }
+}
+
+/// Character marking bytes beneath a dump if colours are disabled.
+fn marker(mark: u8) -> &'static str {
+    match mark {
+        0 => " ",
+        WARNING => "~",
+        _ => "^",
+    }
+}
+
+fn eprint_bytes(bytes: &[u8], marks: &[u8], offset: usize, color: bool) {
+    eprint!("{}", format_bytes(bytes, marks, offset, color));
+}
+
+/// Formats a block as hex dump, marking bytes beneath the lines if
+/// colours are disabled.
+fn format_bytes(bytes: &[u8], marks: &[u8], offset: usize, color: bool) -> String {
+    let mut dump = String::new();
     for n in 0..32 {
-        eprint!("{:08x}: ", n * 16 + offset * BLOCK_SIZE);
+        let mut hex = String::new();
+        let mut text = String::new();
+        let mut markers = String::new();
+        let mut text_markers = String::new();
         for b in 0..16 {
             let pos: usize = n * 16 + b;
             let u = bytes[pos];
-            if marks[pos] == 0 {
-                eprint!("{:02x}", u);
-            } else if marks[pos] == 1 {
-                eprint!("\x1b[0;33m{:02x}\x1b[0m", u);
-            } else {
-                eprint!("\x1b[0;31m{:02x}\x1b[0m", u);
-            }
+            hex.push_str(&colored(&format!("{:02x}", u), marks[pos], color));
+            markers.push_str(&marker(marks[pos]).repeat(2));
             if b % 2 == 1 {
-                eprint!(" ");
+                hex.push(' ');
+                markers.push(' ');
             }
-        }
-        eprint!(" ");
-        for b in 0..16 {
-            let pos = n * 16 + b;
-            let u = bytes[pos];
             let c = if (32..128).contains(&u) {
                 u as char
             } else {
                 '.'
             };
-            if marks[pos] == 0 {
-                eprint!("{}", c);
-            } else if marks[pos] == 1 {
-                eprint!("\x1b[0;33m{}\x1b[0m", c);
-            } else {
-                eprint!("\x1b[0;31m{}\x1b[0m", c);
-            }
+            text.push_str(&colored(&c.to_string(), marks[pos], color));
+            text_markers.push_str(marker(marks[pos]));
+        }
+        dump.push_str(&format!(
+            "{:08x}: {} {}\n",
+            n * 16 + offset * BLOCK_SIZE,
+            hex,
+            text
+        ));
+        if !color && marks[n * 16..n * 16 + 16].iter().any(|&m| m != 0) {
+            let line = format!("{:10}{} {}", "", markers, text_markers);
+            dump.push_str(line.trim_end());
+            dump.push('\n');
         }
-        eprintln!();
     }
+    dump
 }
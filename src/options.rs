@@ -0,0 +1,54 @@
+/// Optional checks which are not needed for portability but enforce
+/// stricter policies on archives.
+#[derive(Clone, Default)]
+pub struct Options {
+    /// Require explicit directory entries for all parent directories.
+    pub parent_directories: bool,
+    /// Reject setuid, setgid and sticky bits.
+    pub special_mode_bits: bool,
+    /// Reject group and world writable entries.
+    pub writable_modes: bool,
+    /// Reject executable bits on files which are neither scripts nor binaries.
+    pub executable_modes: bool,
+    /// Reject directories without execute bit for owner.
+    pub searchable_directories: bool,
+    /// Allow only 0755 for directories and 0644 or 0755 for files.
+    pub canonical_modes: bool,
+    /// Require uid and gid 0.
+    pub numeric_owner: bool,
+    /// Reject user and group names which may belong to a person.
+    pub anonymous_names: bool,
+    /// Reject uid and gid above 2097151 which old readers truncate.
+    pub large_ids: bool,
+    /// Require the same owner for all entries.
+    pub consistent_owners: bool,
+    /// Reject timestamps in the future or at epoch.
+    pub timestamps: bool,
+    /// Require timestamps not newer than given SOURCE_DATE_EPOCH.
+    pub source_date_epoch: Option<u64>,
+    /// Require entries sorted by path.
+    pub sorted_entries: bool,
+    /// Require the same modification time for all entries.
+    pub uniform_timestamps: bool,
+    /// Require empty user and group names.
+    pub empty_names: bool,
+    /// Reject devices and fifos, which have no use in source archives.
+    pub special_files: bool,
+    /// Reject atime and ctime pax records.
+    pub volatile_times: bool,
+    /// Reject pax header names containing process ids.
+    pub pax_header_pids: bool,
+}
+
+impl Options {
+    /// Enables checks for bit-for-bit reproducible archives.
+    pub fn reproducible(&mut self) {
+        self.canonical_modes = true;
+        self.numeric_owner = true;
+        self.sorted_entries = true;
+        self.uniform_timestamps = true;
+        self.empty_names = true;
+        self.volatile_times = true;
+        self.pax_header_pids = true;
+    }
+}
@@ -1,11 +1,19 @@
 use crate::issues::Hint;
 use crate::issues::Issue;
+use core::ops::Range;
+use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 
 pub struct LintPaxExtendedHeader {
     pub keywords: BTreeSet<String>,
+    /// Values of records without trailing newline by keyword.
+    pub records: BTreeMap<String, Vec<u8>>,
+    /// Byte ranges of records in bytes by keyword.
+    pub ranges: BTreeMap<String, Range<usize>>,
     pub hints: BTreeSet<Hint>,
     pub issues: BTreeSet<Issue>,
+    /// Issues and the byte range in bytes of the record they concern.
+    pub locations: Vec<(Issue, Range<usize>)>,
     pub bytes: Vec<u8>,
 }
 
@@ -13,15 +21,24 @@ impl LintPaxExtendedHeader {
     pub fn new(bytes: Vec<u8>) -> LintPaxExtendedHeader {
         let mut result = LintPaxExtendedHeader {
             keywords: BTreeSet::new(),
+            records: BTreeMap::new(),
+            ranges: BTreeMap::new(),
             hints: BTreeSet::new(),
             issues: BTreeSet::new(),
+            locations: Vec::new(),
             bytes,
         };
         result.lint();
         result
     }
 
-    fn lint_header(keywords: &mut BTreeSet<String>, vec: Vec<u8>) -> Option<Issue> {
+    fn lint_header(
+        keywords: &mut BTreeSet<String>,
+        records: &mut BTreeMap<String, Vec<u8>>,
+        ranges: &mut BTreeMap<String, Range<usize>>,
+        vec: Vec<u8>,
+        range: Range<usize>,
+    ) -> Option<Issue> {
         // check newline
         if vec[vec.len() - 1] != b'\n' {
             return Some(Issue::PaxHeaderNoNewline);
@@ -29,21 +46,30 @@ impl LintPaxExtendedHeader {
         // check equal sign
         if let Some(p) = vec.iter().position(|&c| c == b'=') {
             let keyword = &vec[0..p];
-            let _value = &vec[p + 1..vec.len()];
+            let value = &vec[p + 1..vec.len() - 1];
             // check keyword
-            if keyword[0] == b' ' || keyword[0] == b'\t' {
-                println!("{:?}", keyword);
+            if keyword.is_empty() {
+                Some(Issue::PaxHeaderKeywordEmpty)
+            } else if keyword[0] == b' ' || keyword[0] == b'\t' {
                 Some(Issue::PaxHeaderKeywordBlank)
             } else if let Ok(s) = String::from_utf8(keyword.to_vec()) {
                 if keywords.contains(&s) {
                     Some(Issue::PaxHeaderKeywordDuplicate)
-                } else if s.contains("size") {
-                    Some(Issue::PaxSize)
-                } else if s.contains("path") {
-                    Some(Issue::PaxPath)
+                } else if !is_valid_number(&s, value) {
+                    Some(Issue::PaxInvalidNumber)
                 } else {
+                    /* readers without pax support ignore these records */
+                    let issue = if s.contains("size") {
+                        Some(Issue::PaxSize)
+                    } else if s.contains("path") {
+                        Some(Issue::PaxPath)
+                    } else {
+                        None
+                    };
+                    records.insert(s.clone(), value.to_vec());
+                    ranges.insert(s.clone(), range);
                     keywords.insert(s);
-                    None
+                    issue
                 }
             } else {
                 Some(Issue::PaxHeaderKeywordNoUtf8)
@@ -56,9 +82,10 @@ impl LintPaxExtendedHeader {
     fn lint(&mut self) {
         let mut start = 0;
         if self.bytes.is_empty() {
-            self.issues.insert(Issue::PaxEmpty);
+            self.report(Issue::PaxEmpty, 0..0);
         }
-        while start < self.bytes.len() && self.issues.is_empty() {
+        while start < self.bytes.len() && self.fatal().is_none() {
+            let record = start;
             // check blank
             if let Some(p) = self.bytes.iter().skip(start).position(|&c| c == b' ') {
                 // check size
@@ -70,24 +97,68 @@ impl LintPaxExtendedHeader {
                                 Some(Issue::PaxHeaderSizeOctal)
                             } else if n > i32::MAX as u64 {
                                 Some(Issue::PaxHeaderSizeTooLarge)
+                            } else if start + n as usize > self.bytes.len()
+                                || (n as usize) < s.len() + 2
+                            {
+                                /* record must fit into data and contain a newline */
+                                Some(Issue::PaxHeaderSizeInvalid)
                             } else {
                                 let header_slice =
                                     &self.bytes[(start + s.len() + 1)..(start + n as usize)];
                                 start += n as usize;
-                                Self::lint_header(&mut self.keywords, header_slice.to_vec())
+                                Self::lint_header(
+                                    &mut self.keywords,
+                                    &mut self.records,
+                                    &mut self.ranges,
+                                    header_slice.to_vec(),
+                                    record..start,
+                                )
                             }
                         }
                         _ => Some(Issue::PaxHeaderSizeInvalid),
                     };
                     if let Some(i) = issue {
-                        self.issues.insert(i);
+                        let end = start.max(record + p);
+                        self.report(i, record..end);
                     }
                 } else {
-                    self.issues.insert(Issue::PaxHeaderSizeInvalid);
+                    self.report(Issue::PaxHeaderSizeInvalid, record..record + p);
                 }
             } else {
-                self.issues.insert(Issue::PaxHeaderSizeInvalid);
+                self.report(Issue::PaxHeaderSizeInvalid, record..self.bytes.len());
             }
         }
     }
+
+    /// Returns the first issue which prevents reading the records and
+    /// the bytes it concerns. Path and size records are read, even though
+    /// readers without pax support ignore them.
+    pub fn fatal(&self) -> Option<&(Issue, Range<usize>)> {
+        self.locations
+            .iter()
+            .find(|(i, _)| *i != Issue::PaxPath && *i != Issue::PaxSize)
+    }
+
+    /// Records an issue and the bytes of the record it concerns.
+    fn report(&mut self, issue: Issue, range: Range<usize>) {
+        self.issues.insert(issue);
+        self.locations.push((issue, range));
+    }
+}
+
+/// Checks that value of a numeric record is a decimal number which fits
+/// into the header. Times may have a fraction.
+fn is_valid_number(keyword: &str, value: &[u8]) -> bool {
+    let number = |v: &[u8]| {
+        v.iter().all(|c| c.is_ascii_digit())
+            && str::from_utf8(v).is_ok_and(|s| s.parse::<u64>().is_ok())
+    };
+    match keyword {
+        "gid" | "size" | "uid" => number(value),
+        "atime" | "ctime" | "mtime" => match value.iter().position(|&c| c == b'.') {
+            Some(p) => number(&value[..p]) && value[p + 1..].iter().all(|c| c.is_ascii_digit()),
+            None => number(value),
+        },
+        _ => true,
+    }
 }
@@ -0,0 +1,257 @@
+use crate::header::BLOCK_SIZE;
+use crate::header::GID_RANGE;
+use crate::header::GNAME_RANGE;
+use crate::header::LintHeader;
+use crate::header::MODE_RANGE;
+use crate::header::MTIME_RANGE;
+use crate::header::NAME_RANGE;
+use crate::header::UID_RANGE;
+use crate::header::UNAME_RANGE;
+use crate::issues::Issue;
+use crate::options::Options;
+use crate::pax::LintPaxExtendedHeader;
+use core::cmp::Ordering;
+use core::ops::Range;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+/// Magic bytes of scripts and executable binary formats.
+const EXECUTABLE_MAGICS: [&[u8]; 8] = [
+    b"#!",
+    b"\x7fELF",
+    b"MZ",
+    b"\xfe\xed\xfa\xce",
+    b"\xfe\xed\xfa\xcf",
+    b"\xce\xfa\xed\xfe",
+    b"\xcf\xfa\xed\xfe",
+    b"\xca\xfe\xba\xbe",
+];
+
+/// Largest id which fits into an octal uid or gid field.
+pub const MAX_OCTAL_ID: u64 = 0o7777777;
+
+/// Largest timestamp which fits into an octal mtime field, reached in 2242.
+pub const MAX_OCTAL_TIME: u64 = 0o77777777777;
+
+/// User and group names which do not reveal personal information.
+const ANONYMOUS_NAMES: [&str; 8] = [
+    "", "bin", "daemon", "nobody", "nogroup", "root", "users", "wheel",
+];
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Owner {
+    pub uid: u64,
+    pub gid: u64,
+    pub uname: String,
+    pub gname: String,
+}
+
+impl Owner {
+    pub fn new(header: &LintHeader) -> Owner {
+        Owner {
+            uid: header.uid,
+            gid: header.gid,
+            uname: header.uname.clone(),
+            gname: header.gname.clone(),
+        }
+    }
+}
+
+/// Checks ownership of header against enabled ownership policies. First
+/// keeps the owner of the first entry for consistency checks.
+pub fn lint_owner(
+    header: &LintHeader,
+    options: &Options,
+    first: &mut Option<Owner>,
+) -> Vec<(Issue, Range<usize>)> {
+    let mut issues = Vec::new();
+    if header.typeflag == b'g' || header.typeflag == b'x' {
+        return issues;
+    }
+    /*
+     * Values this large can only be set through pax headers. Readers
+     * without pax support use the truncated values of the ustar fields.
+     */
+    if options.large_ids {
+        if header.uid > MAX_OCTAL_ID {
+            issues.push((Issue::LargeId, UID_RANGE));
+        }
+        if header.gid > MAX_OCTAL_ID {
+            issues.push((Issue::LargeId, GID_RANGE));
+        }
+    }
+    if options.numeric_owner {
+        if header.uid != 0 {
+            issues.push((Issue::NonRootOwner, UID_RANGE));
+        }
+        if header.gid != 0 {
+            issues.push((Issue::NonRootOwner, GID_RANGE));
+        }
+    }
+    if options.anonymous_names {
+        if !ANONYMOUS_NAMES.contains(&header.uname.as_str()) {
+            issues.push((Issue::PersonalOwnerName, UNAME_RANGE));
+        }
+        if !ANONYMOUS_NAMES.contains(&header.gname.as_str()) {
+            issues.push((Issue::PersonalOwnerName, GNAME_RANGE));
+        }
+    }
+    if options.consistent_owners {
+        let owner = Owner::new(header);
+        match first {
+            Some(o) if *o != owner => {
+                issues.push((Issue::InconsistentOwner, UID_RANGE.start..GID_RANGE.end));
+                issues.push((Issue::InconsistentOwner, UNAME_RANGE.start..GNAME_RANGE.end));
+            }
+            Some(_) => {}
+            None => *first = Some(owner),
+        }
+    }
+    issues
+}
+
+/// Checks modification time of header against enabled timestamp policies.
+pub fn lint_mtime(header: &LintHeader, options: &Options) -> Vec<(Issue, Range<usize>)> {
+    let mut issues = Vec::new();
+    if header.typeflag == b'g' || header.typeflag == b'x' {
+        return Vec::new();
+    }
+    if header.mtime > MAX_OCTAL_TIME {
+        issues.push(Issue::LargeTimestamp);
+    }
+    if options.timestamps {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        if header.mtime > now {
+            issues.push(Issue::FutureTimestamp);
+        }
+        if header.mtime == 0 {
+            issues.push(Issue::ZeroTimestamp);
+        }
+    }
+    if let Some(epoch) = options.source_date_epoch
+        && (header.mtime > epoch || (header.mtime == epoch && header.mtime_nanos != 0))
+    {
+        issues.push(Issue::UnclampedTimestamp);
+    }
+    issues.into_iter().map(|i| (i, MTIME_RANGE)).collect()
+}
+
+/// Compares paths component by component, which keeps the contents of a
+/// directory right after the directory itself.
+pub fn compare_paths(a: &str, b: &str) -> Ordering {
+    a.split('/').cmp(b.split('/'))
+}
+
+/// Checks header and records of extended header for sources of
+/// non-determinism. First keeps the modification time of the first entry
+/// for consistency checks.
+pub fn lint_reproducible(
+    header: &LintHeader,
+    extended: Option<&LintPaxExtendedHeader>,
+    options: &Options,
+    first: &mut Option<u64>,
+) -> Vec<(Issue, Range<usize>)> {
+    let mut issues = Vec::new();
+    if header.typeflag == b'g' || header.typeflag == b'x' {
+        /*
+         * GNU tar names pax headers %d/PaxHeaders.%p/%f by default,
+         * with %p being the process id of tar.
+         */
+        let pid = header.path.split('/').any(|c| {
+            c.strip_prefix("PaxHeaders.")
+                .is_some_and(|p| !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit()))
+        });
+        if options.pax_header_pids && pid {
+            issues.push((Issue::PaxHeaderPid, NAME_RANGE));
+        }
+        /* ranges of records are relative to the data block */
+        if options.volatile_times
+            && let Some(p) = extended
+        {
+            for keyword in ["atime", "ctime"] {
+                if let Some(r) = p.ranges.get(keyword) {
+                    let range = BLOCK_SIZE + r.start..BLOCK_SIZE + r.end;
+                    issues.push((Issue::PaxVolatileTime, range));
+                }
+            }
+        }
+        return issues;
+    }
+    if options.uniform_timestamps {
+        match first {
+            Some(t) if *t != header.mtime => issues.push((Issue::NonUniformTimestamp, MTIME_RANGE)),
+            Some(_) => {}
+            None => *first = Some(header.mtime),
+        }
+    }
+    if options.empty_names {
+        if !header.uname.is_empty() {
+            issues.push((Issue::OwnerName, UNAME_RANGE));
+        }
+        if !header.gname.is_empty() {
+            issues.push((Issue::OwnerName, GNAME_RANGE));
+        }
+    }
+    issues
+}
+
+/// Checks mode of header against enabled mode policies. Head contains the
+/// first data block of entry, if any.
+pub fn lint_mode(
+    header: &LintHeader,
+    head: &[u8],
+    options: &Options,
+) -> Vec<(Issue, Range<usize>)> {
+    let mut issues = Vec::new();
+    let mode = header.mode;
+    let typeflag = header.typeflag;
+    let regular = typeflag == b'0' || typeflag == b'7';
+    if typeflag == b'g' || typeflag == b'x' {
+        return Vec::new();
+    }
+    if options.special_mode_bits {
+        if mode & 0o4000 != 0 {
+            issues.push(Issue::SetUidMode);
+        }
+        if mode & 0o2000 != 0 {
+            issues.push(Issue::SetGidMode);
+        }
+        if mode & 0o1000 != 0 {
+            issues.push(Issue::StickyMode);
+        }
+    }
+    /*
+     * Symbolic links usually have mode 0777, which is ignored by
+     * extractors anyway.
+     */
+    if options.writable_modes && typeflag != b'2' {
+        if mode & 0o002 != 0 {
+            issues.push(Issue::WorldWritableMode);
+        } else if mode & 0o020 != 0 {
+            issues.push(Issue::GroupWritableMode);
+        }
+    }
+    if options.executable_modes
+        && regular
+        && mode & 0o111 != 0
+        && !EXECUTABLE_MAGICS.iter().any(|m| head.starts_with(m))
+    {
+        issues.push(Issue::ExecutableMode);
+    }
+    if options.searchable_directories && typeflag == b'5' && mode & 0o100 == 0 {
+        issues.push(Issue::DirectoryNotSearchable);
+    }
+    if options.canonical_modes {
+        let canonical = match typeflag {
+            b'5' => mode == 0o755,
+            b'1' | b'2' => true,
+            _ => mode == 0o644 || mode == 0o755,
+        };
+        if !canonical {
+            issues.push(Issue::NonCanonicalMode);
+        }
+    }
+    issues.into_iter().map(|i| (i, MODE_RANGE)).collect()
+}
@@ -0,0 +1,62 @@
+use crate::header::LintHeader;
+use crate::issues::Severity;
+use core::ops::Range;
+
+/// Violation of a custom rule, reported as finding of
+/// `Issue::RuleViolation`.
+pub struct Violation {
+    /// Code identifying the rule.
+    pub code: String,
+    pub message: String,
+    /// Header field which is marked in the dump. Bytes beyond the header
+    /// block are not marked.
+    pub range: Option<Range<usize>>,
+    pub severity: Severity,
+}
+
+impl Violation {
+    pub fn new(code: &str, message: &str) -> Violation {
+        Violation {
+            code: code.to_string(),
+            message: message.to_string(),
+            range: None,
+            severity: Severity::Warning,
+        }
+    }
+
+    /// Marks header field in the dump.
+    pub fn with_range(mut self, range: Range<usize>) -> Violation {
+        self.range = Some(range);
+        self
+    }
+
+    pub fn with_severity(mut self, severity: Severity) -> Violation {
+        self.severity = severity;
+        self
+    }
+}
+
+/// Custom lint rule which is informed about archive events.
+///
+/// Extended headers like pax headers and GNU long names are not passed
+/// as entries, their content is already applied to the header of the
+/// following entry. Entries of a concatenated archive are passed as well.
+/// The end is only reached if no fatal issue was found.
+pub trait Rule {
+    fn start(&mut self) -> Vec<Violation> {
+        Vec::new()
+    }
+
+    fn entry(&mut self, _header: &LintHeader, _offset: usize) -> Vec<Violation> {
+        Vec::new()
+    }
+
+    /// Receives data of the current entry in chunks, without padding.
+    fn data(&mut self, _header: &LintHeader, _chunk: &[u8]) -> Vec<Violation> {
+        Vec::new()
+    }
+
+    fn end(&mut self) -> Vec<Violation> {
+        Vec::new()
+    }
+}
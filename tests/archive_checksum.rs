@@ -0,0 +1,79 @@
+mod common;
+
+use alquitran::header::CKSUM_RANGE;
+use alquitran::header::Checksum;
+use alquitran::header::LintHeader;
+use alquitran::header::calculate_checksum;
+use alquitran::header::calculate_signed_checksum;
+use alquitran::issues::Issue;
+use common::entry;
+use common::finish;
+use common::header;
+use common::lint;
+
+#[test]
+fn unsigned() {
+    let header = LintHeader::new(header("caf\u{e9}", b'0', 0));
+    assert_eq!(Checksum::Unsigned, header.checksum);
+    assert!(!header.issues.contains(&Issue::SignedChecksum));
+}
+
+#[test]
+fn signed() {
+    let mut block = header("caf\u{e9}", b'0', 0);
+    let sum = calculate_signed_checksum(&block);
+    assert_ne!(sum as u64, calculate_checksum(&block));
+    block[CKSUM_RANGE].copy_from_slice(format!("{:06o}\0 ", sum).as_bytes());
+    let header = LintHeader::new(block);
+    assert_eq!(Checksum::Signed, header.checksum);
+    assert!(header.issues.contains(&Issue::SignedChecksum));
+    assert!(!header.issues.contains(&Issue::InvalidChecksum));
+}
+
+#[test]
+fn leading_spaces() {
+    let mut block = header("file", b'0', 0);
+    let sum = calculate_checksum(&block);
+    block[CKSUM_RANGE].copy_from_slice(format!("  {:05o}\0", sum).as_bytes());
+    let header = LintHeader::new(block);
+    assert_eq!(Checksum::Unsigned, header.checksum);
+    assert!(header.issues.contains(&Issue::UnusualChecksumFormat));
+    assert!(!header.issues.contains(&Issue::NoNumber));
+}
+
+#[test]
+fn unterminated() {
+    let mut block = header("file", b'0', 0);
+    let sum = calculate_checksum(&block);
+    block[CKSUM_RANGE].copy_from_slice(format!("{:08o}", sum).as_bytes());
+    let header = LintHeader::new(block);
+    assert_eq!(Checksum::Unsigned, header.checksum);
+    assert!(header.issues.contains(&Issue::UnusualChecksumFormat));
+}
+
+#[test]
+fn corrupt() {
+    let mut block = header("file", b'0', 0);
+    let sum = calculate_checksum(&block) + 1;
+    block[CKSUM_RANGE].copy_from_slice(format!("{:06o}\0 ", sum).as_bytes());
+    let header = LintHeader::new(block);
+    assert_eq!(Checksum::Invalid, header.checksum);
+    assert!(header.issues.contains(&Issue::InvalidChecksum));
+}
+
+#[test]
+fn signed_archive() {
+    let mut block = header("caf\u{e9}", b'0', 0);
+    let sum = calculate_signed_checksum(&block);
+    block[CKSUM_RANGE].copy_from_slice(format!("{:06o}\0 ", sum).as_bytes());
+    let mut archive = Vec::new();
+    entry(&mut archive, block, b"");
+    entry(&mut archive, header("next", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::SignedChecksum));
+    assert_eq!(vec!["caf\u{e9}", "next"], result.entries);
+    assert!(result.records.is_some());
+    assert_eq!(Issue::SignedChecksum, result.findings[0].issue);
+    assert_eq!(Some("chksum"), result.findings[0].field);
+}
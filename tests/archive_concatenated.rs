@@ -0,0 +1,68 @@
+mod common;
+
+use alquitran::error::Error;
+use alquitran::issues::Issue;
+use common::entry;
+use common::finish;
+use common::header;
+use common::lint;
+use common::pax;
+
+#[test]
+fn concatenated_archive() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("a", b'0', 1), b"a");
+    finish(&mut archive);
+    archive.resize(archive.len() + 1024, 0);
+    entry(&mut archive, header("b", b'0', 1), b"b");
+    entry(&mut archive, header("c", b'0', 1), b"c");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::ConcatenatedArchive));
+    assert!(!result.issues.contains(&Issue::TrailingByteNotNul));
+    assert_eq!(vec!["a"], result.entries);
+    assert_eq!(1024, result.records.unwrap().padding);
+    let embedded = result.concatenated.unwrap();
+    assert_eq!(6, embedded.offset);
+    assert_eq!(vec!["b", "c"], embedded.entries);
+    assert!(embedded.is_portable());
+}
+
+#[test]
+fn trailing_garbage() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("a", b'0', 1), b"a");
+    finish(&mut archive);
+    archive.extend_from_slice(&[1; 512]);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::TrailingByteNotNul));
+    assert!(!result.issues.contains(&Issue::ConcatenatedArchive));
+    assert!(result.concatenated.is_none());
+}
+
+#[test]
+fn truncated_hidden_archive() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("a", b'0', 1), b"a");
+    finish(&mut archive);
+    entry(&mut archive, header("b", b'0', 1), b"b");
+    archive.extend_from_slice(&header("c", b'0', 1024));
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::ConcatenatedArchive));
+    let embedded = result.concatenated.unwrap();
+    assert_eq!(vec!["b"], embedded.entries);
+    assert!(matches!(embedded.error, Some(Error::Truncated { .. })));
+}
+
+#[test]
+fn hidden_pax_archive() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("a", b'0', 1), b"a");
+    finish(&mut archive);
+    pax(&mut archive, "PaxHeaders/b", &[("mtime", "1")]);
+    entry(&mut archive, header("b", b'0', 1), b"b");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::ConcatenatedArchive));
+    assert_eq!(vec!["b"], result.concatenated.unwrap().entries);
+}
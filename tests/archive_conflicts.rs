@@ -0,0 +1,61 @@
+mod common;
+
+use alquitran::issues::Issue;
+use common::entry;
+use common::finish;
+use common::header;
+use common::lint;
+use common::pax;
+
+#[test]
+fn parent_not_directory() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("foo", b'0', 0), b"");
+    entry(&mut archive, header("foo/bar", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::ParentNotDirectory));
+    assert_eq!(1, result.dump.unwrap().offset);
+}
+
+#[test]
+fn directory_replaces_file() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("foo", b'0', 0), b"");
+    entry(&mut archive, header("foo/", b'5', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::DirectoryReplacesFile));
+    assert!(result.duplicated_paths.is_empty());
+}
+
+#[test]
+fn file_replaces_directory() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("foo/", b'5', 0), b"");
+    entry(&mut archive, header("foo", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::FileReplacesDirectory));
+}
+
+#[test]
+fn file_replaces_implicit_directory() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("foo/bar", b'0', 0), b"");
+    entry(&mut archive, header("foo", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::FileReplacesDirectory));
+}
+
+#[test]
+fn extended_header_path() {
+    let mut archive = Vec::new();
+    pax(&mut archive, "PaxHeaders/foo", &[("uid", "1000")]);
+    entry(&mut archive, header("foo", b'0', 0), b"");
+    entry(&mut archive, header("PaxHeaders", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(!result.issues.contains(&Issue::FileReplacesDirectory));
+}
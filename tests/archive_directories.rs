@@ -0,0 +1,51 @@
+mod common;
+
+use alquitran::issues::Issue;
+use alquitran::options::Options;
+use common::entry;
+use common::finish;
+use common::header;
+use common::lint;
+use common::lint_with_options;
+
+fn options() -> Options {
+    Options {
+        parent_directories: true,
+        ..Options::default()
+    }
+}
+
+#[test]
+fn explicit_parent_directories() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("a/", b'5', 0), b"");
+    entry(&mut archive, header("a/b/", b'5', 0), b"");
+    entry(&mut archive, header("a/b/c", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint_with_options(archive, options());
+    assert!(result.is_portable());
+}
+
+#[test]
+fn missing_parent_directories() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("a/b/c", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint_with_options(archive.clone(), options());
+    assert!(result.issues.contains(&Issue::MissingParentDirectory));
+    assert_eq!(2, result.missing_directories.len());
+    assert!(result.missing_directories.contains("a/b"));
+    assert!(lint(archive).is_portable());
+}
+
+#[test]
+fn late_parent_directory() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("a/b", b'0', 0), b"");
+    entry(&mut archive, header("a/", b'5', 0), b"");
+    finish(&mut archive);
+    let result = lint_with_options(archive, options());
+    assert!(result.issues.contains(&Issue::LateParentDirectory));
+    assert!(!result.issues.contains(&Issue::MissingParentDirectory));
+    assert!(result.late_directories.contains("a"));
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use alquitran::divergence::Implementation;
+use alquitran::divergence::interpret;
+use alquitran::header::MAGIC_RANGE;
+use alquitran::header::PREFIX_RANGE;
+use alquitran::header::SIZE_RANGE;
+use alquitran::issues::Issue;
+use common::entry;
+use common::finish;
+use common::header;
+use common::lint;
+use common::pax;
+use common::set_checksum;
+use common::set_string;
+use std::collections::BTreeMap;
+
+#[test]
+fn agreement() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("dir/", b'5', 0), b"");
+    entry(&mut archive, header("dir/file", b'0', 3), b"abc");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.divergences.is_empty());
+}
+
+#[test]
+fn regular_file_with_slash() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("dir/", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::ImplementationDivergence));
+    assert_eq!(1, result.divergences.len());
+    for (implementation, interpretation) in result.divergences[0].interpretations.iter() {
+        let typeflag = interpretation.as_ref().unwrap().typeflag;
+        match implementation {
+            Implementation::Go | Implementation::Python => assert_eq!(b'0', typeflag),
+            _ => assert_eq!(b'5', typeflag),
+        }
+    }
+}
+
+#[test]
+fn gnu_prefix() {
+    let mut block = header("file", b'0', 0);
+    block[MAGIC_RANGE.start..MAGIC_RANGE.start + 8].copy_from_slice(b"ustar  \0");
+    set_string(&mut block, PREFIX_RANGE, "dir");
+    set_checksum(&mut block);
+    let mut archive = Vec::new();
+    entry(&mut archive, block, b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert_eq!(1, result.divergences.len());
+    for (implementation, interpretation) in result.divergences[0].interpretations.iter() {
+        let path = &interpretation.as_ref().unwrap().path;
+        match implementation {
+            Implementation::Python => assert_eq!("dir/file", path),
+            _ => assert_eq!("file", path),
+        }
+    }
+}
+
+#[test]
+fn unknown_magic() {
+    let mut block = header("file", b'0', 0);
+    block[MAGIC_RANGE.start..MAGIC_RANGE.start + 8].copy_from_slice(b"tar\x00\x00\x0000");
+    set_checksum(&mut block);
+    let mut archive = Vec::new();
+    entry(&mut archive, block, b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert_eq!(1, result.divergences.len());
+    for (implementation, interpretation) in result.divergences[0].interpretations.iter() {
+        match implementation {
+            Implementation::Busybox => assert!(interpretation.is_none()),
+            _ => assert!(interpretation.is_some()),
+        }
+    }
+}
+
+#[test]
+fn pax_size() {
+    let block = header("file", b'0', 0);
+    let mut records = BTreeMap::new();
+    records.insert("size".to_string(), b"5".to_vec());
+    let busybox = interpret(Implementation::Busybox, &block, &records, None).unwrap();
+    let gnu = interpret(Implementation::GnuTar, &block, &records, None).unwrap();
+    assert_eq!(0, busybox.size);
+    assert_eq!(5, gnu.size);
+}
+
+#[test]
+fn pax_size_archive() {
+    let mut archive = Vec::new();
+    pax(&mut archive, "PaxHeaders/file", &[("size", "3")]);
+    entry(&mut archive, header("file", b'0', 0), b"abc");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::PaxSize));
+    assert_eq!(vec!["file"], result.entries);
+    assert_eq!(1, result.divergences.len());
+    assert_eq!(SIZE_RANGE, result.divergences[0].range);
+    for (implementation, interpretation) in result.divergences[0].interpretations.iter() {
+        let size = interpretation.as_ref().unwrap().size;
+        match implementation {
+            Implementation::Busybox => assert_eq!(0, size),
+            _ => assert_eq!(3, size),
+        }
+    }
+}
+
+#[test]
+fn pax_path_archive() {
+    let mut archive = Vec::new();
+    pax(&mut archive, "PaxHeaders/file", &[("path", "../escaped")]);
+    entry(&mut archive, header("file", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::PaxPath));
+    assert!(result.issues.contains(&Issue::DotDotPath));
+    assert!(result.divergences.is_empty());
+}
@@ -0,0 +1,182 @@
+mod common;
+
+use alquitran::archive::Archive;
+use alquitran::header::BLOCK_SIZE;
+use alquitran::issues::Issue;
+use common::entry;
+use common::finish;
+use common::header;
+use common::pax;
+use std::io::Cursor;
+use std::io::Read;
+
+fn archive(bytes: Vec<u8>) -> Archive {
+    Archive::new(Box::new(Cursor::new(bytes)))
+}
+
+#[test]
+fn headers_and_data() {
+    let mut bytes = Vec::new();
+    entry(&mut bytes, header("dir/", b'5', 0), b"");
+    entry(&mut bytes, header("dir/file", b'0', 600), &[b'a'; 600]);
+    entry(&mut bytes, header("dir/next", b'0', 3), b"xyz");
+    finish(&mut bytes);
+    let mut archive = archive(bytes);
+    let mut entries = archive.entries();
+    let mut seen = Vec::new();
+    for entry in entries.by_ref() {
+        let mut entry = entry.unwrap();
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data).unwrap();
+        seen.push((entry.header.path.clone(), entry.offset, data.len()));
+    }
+    assert_eq!(
+        vec![
+            ("dir/".to_string(), 0, 0),
+            ("dir/file".to_string(), 1, 600),
+            ("dir/next".to_string(), 4, 3)
+        ],
+        seen
+    );
+    assert_eq!(8, entries.offset());
+}
+
+#[test]
+fn data_offsets() {
+    let mut bytes = Vec::new();
+    pax(&mut bytes, "PaxHeaders/x", &[("uid", "1000")]);
+    entry(&mut bytes, header("x", b'0', 3), b"abc");
+    finish(&mut bytes);
+    let mut archive = archive(bytes);
+    let offsets: Vec<(usize, usize)> = archive
+        .entries()
+        .map(|e| e.unwrap())
+        .map(|e| (e.offset, e.data_offset()))
+        .collect();
+    assert_eq!(vec![(0, 1), (2, 3)], offsets);
+}
+
+#[test]
+fn unread_data_is_skipped() {
+    let mut bytes = Vec::new();
+    entry(&mut bytes, header("a", b'0', 1024), &[1; 1024]);
+    entry(&mut bytes, header("b", b'0', 0), b"");
+    finish(&mut bytes);
+    let mut archive = archive(bytes);
+    let paths: Vec<String> = archive.entries().map(|e| e.unwrap().header.path).collect();
+    assert_eq!(vec!["a", "b"], paths);
+}
+
+#[test]
+fn data_after_advancing() {
+    let mut bytes = Vec::new();
+    entry(&mut bytes, header("a", b'0', 3), b"abc");
+    entry(&mut bytes, header("b", b'0', 0), b"");
+    finish(&mut bytes);
+    let mut archive = archive(bytes);
+    let mut entries = archive.entries();
+    let mut first = entries.next().unwrap().unwrap();
+    entries.next().unwrap().unwrap();
+    let mut data = Vec::new();
+    assert!(first.read_to_end(&mut data).is_err());
+}
+
+#[test]
+fn pax_attributes() {
+    let mut bytes = Vec::new();
+    pax(
+        &mut bytes,
+        "PaxHeaders/file",
+        &[("uid", "1000"), ("uname", "user")],
+    );
+    entry(&mut bytes, header("file", b'0', 0), b"");
+    finish(&mut bytes);
+    let mut archive = archive(bytes);
+    let entries: Vec<_> = archive.entries().map(|e| e.unwrap()).collect();
+    assert_eq!(2, entries.len());
+    assert!(entries[0].extended.is_some());
+    assert_eq!(1000, entries[1].header.uid);
+    assert_eq!("user", entries[1].header.uname);
+    assert_eq!(b"user", &entries[1].header.pax["uname"][..]);
+}
+
+#[test]
+fn last_block() {
+    let mut bytes = Vec::new();
+    let mut data = vec![b'a'; 700];
+    data.resize(1024, 0);
+    data[1000] = 1;
+    bytes.extend_from_slice(&header("file", b'0', 700));
+    bytes.extend_from_slice(&data);
+    finish(&mut bytes);
+    let mut archive = archive(bytes);
+    let mut entry = archive.entries().next().unwrap().unwrap();
+    let (offset, block) = entry.last_block().unwrap().unwrap();
+    assert_eq!(2, offset);
+    assert_eq!([b'a'; 188], block[..188]);
+    assert_eq!(1, block[1000 - BLOCK_SIZE]);
+}
+
+#[test]
+fn stops_after_invalid_header() {
+    let mut bytes = Vec::new();
+    let mut block = header("file", b'0', 0);
+    block[0] = b'x';
+    entry(&mut bytes, block, b"");
+    entry(&mut bytes, header("next", b'0', 0), b"");
+    finish(&mut bytes);
+    let mut archive = archive(bytes);
+    let entries: Vec<_> = archive.entries().map(|e| e.unwrap()).collect();
+    assert_eq!(1, entries.len());
+    assert!(entries[0].issues().contains(&Issue::InvalidChecksum));
+}
+
+#[test]
+fn truncated() {
+    let mut bytes = Vec::new();
+    entry(&mut bytes, header("file", b'0', 3), b"abc");
+    let mut archive = archive(bytes);
+    let mut entries = archive.entries();
+    assert!(entries.next().unwrap().is_ok());
+    assert!(entries.next().unwrap().is_err());
+    assert!(entries.next().is_none());
+}
+
+#[test]
+fn oversized_extended_header() {
+    let mut bytes = Vec::new();
+    entry(&mut bytes, header("PaxHeaders/x", b'x', 0o77777777777), b"");
+    finish(&mut bytes);
+    let mut archive = archive(bytes);
+    let entries: Vec<_> = archive.entries().map(|e| e.unwrap()).collect();
+    assert_eq!(1, entries.len());
+    assert!(
+        entries[0]
+            .header
+            .issues
+            .contains(&Issue::ExtendedHeaderTooLarge)
+    );
+    assert!(entries[0].is_empty());
+}
+
+#[test]
+fn malformed_pax_records() {
+    for (data, issue) in [
+        (&b"5 =a\n"[..], Issue::PaxHeaderKeywordEmpty),
+        (&b"99 path=a\n"[..], Issue::PaxHeaderSizeInvalid),
+        (&b"2 x"[..], Issue::PaxHeaderSizeInvalid),
+    ] {
+        let mut bytes = Vec::new();
+        entry(
+            &mut bytes,
+            header("PaxHeaders/a", b'x', data.len() as u64),
+            data,
+        );
+        entry(&mut bytes, header("a", b'0', 0), b"");
+        finish(&mut bytes);
+        let mut archive = archive(bytes);
+        let entries: Vec<_> = archive.entries().map(|e| e.unwrap()).collect();
+        assert_eq!(1, entries.len());
+        assert!(entries[0].issues().contains(&issue));
+    }
+}
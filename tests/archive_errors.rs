@@ -0,0 +1,70 @@
+mod common;
+
+use alquitran::archive::Archive;
+use alquitran::error::Error;
+use common::entry;
+use common::finish;
+use common::header;
+use std::error::Error as _;
+use std::io;
+use std::io::Cursor;
+use std::io::Read;
+
+/// Reader which fails after given bytes.
+struct Failing(Cursor<Vec<u8>>);
+
+impl Read for Failing {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.0.read(buf)? {
+            0 => Err(io::Error::other("device gone")),
+            n => Ok(n),
+        }
+    }
+}
+
+#[test]
+fn truncated_header() {
+    let mut bytes = Vec::new();
+    entry(&mut bytes, header("a", b'0', 3), b"abc");
+    bytes.extend_from_slice(&[1; 100]);
+    let result = Archive::new(Box::new(Cursor::new(bytes))).lint();
+    let Err(Error::Truncated { offset }) = result else {
+        panic!("archive is not truncated");
+    };
+    assert_eq!(1024, offset);
+}
+
+#[test]
+fn truncated_data() {
+    let mut bytes = Vec::new();
+    entry(&mut bytes, header("a", b'0', 2048), b"abc");
+    let result = Archive::new(Box::new(Cursor::new(bytes))).lint();
+    assert!(matches!(result, Err(Error::Truncated { .. })));
+}
+
+#[test]
+fn io_error_has_source() {
+    let mut bytes = Vec::new();
+    entry(&mut bytes, header("a", b'0', 3), b"abc");
+    let reader = Failing(Cursor::new(bytes));
+    let error = Archive::new(Box::new(reader)).lint().err().unwrap();
+    assert!(matches!(error, Error::Io { offset: 1024, .. }));
+    assert_eq!("device gone", error.source().unwrap().to_string());
+}
+
+#[test]
+fn moved_past_data() {
+    let mut bytes = Vec::new();
+    entry(&mut bytes, header("a", b'0', 3), b"abc");
+    entry(&mut bytes, header("b", b'0', 0), b"");
+    finish(&mut bytes);
+    let mut archive = Archive::new(Box::new(Cursor::new(bytes)));
+    let mut entries = archive.entries();
+    let mut first = entries.next().unwrap().unwrap();
+    entries.next().unwrap().unwrap();
+    let error = first.last_block().err().unwrap();
+    assert!(matches!(error, Error::Moved { offset: 1536 }));
+    let error = first.read(&mut [0; 3]).err().unwrap();
+    let inner = error.get_ref().unwrap().downcast_ref::<Error>();
+    assert!(matches!(inner, Some(Error::Moved { .. })));
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use alquitran::archive::Archive;
+use alquitran::archive::EXIT_ERRORS;
+use alquitran::archive::EXIT_HINTS;
+use alquitran::archive::EXIT_ISSUES;
+use alquitran::archive::EXIT_POLICY;
+use alquitran::archive::RECORD_SIZE;
+use alquitran::header::LintHeader;
+use alquitran::header::MODE_RANGE;
+use alquitran::issues::Issue;
+use alquitran::issues::Severity;
+use alquitran::options::Options;
+use alquitran::rule::Rule;
+use alquitran::rule::Violation;
+use common::entry;
+use common::finish;
+use common::header;
+use common::lint;
+use common::lint_with_options;
+use common::set_checksum;
+use common::set_number;
+use std::io::Cursor;
+
+/// Pads archive to a full record to avoid `Hint::PartialRecord`.
+fn finish_record(archive: &mut Vec<u8>) {
+    finish(archive);
+    archive.resize(archive.len().next_multiple_of(RECORD_SIZE as usize), 0);
+}
+
+#[test]
+fn portable() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("a", b'0', 1), b"a");
+    finish_record(&mut archive);
+    assert_eq!(0, lint(archive).exit_code());
+}
+
+#[test]
+fn hints() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("a", b'0', 1), b"a");
+    finish(&mut archive);
+    assert_eq!(EXIT_HINTS, lint(archive).exit_code());
+}
+
+#[test]
+fn policy() {
+    let mut block = header("a", b'0', 0);
+    set_number(&mut block, MODE_RANGE, 0o4644);
+    set_checksum(&mut block);
+    let mut archive = Vec::new();
+    entry(&mut archive, block, b"");
+    finish_record(&mut archive);
+    let options = Options {
+        special_mode_bits: true,
+        ..Default::default()
+    };
+    assert_eq!(EXIT_POLICY, lint_with_options(archive, options).exit_code());
+}
+
+#[test]
+fn reproducible_unsorted() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("b", b'0', 0), b"");
+    entry(&mut archive, header("a", b'0', 0), b"");
+    finish_record(&mut archive);
+    let mut options = Options::default();
+    options.reproducible();
+    let result = lint_with_options(archive, options);
+    assert!(result.issues.contains(&Issue::UnsortedEntries));
+    assert_eq!(EXIT_POLICY, result.exit_code());
+}
+
+#[test]
+fn missing_parent_directory() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("d/f", b'0', 0), b"");
+    finish_record(&mut archive);
+    let options = Options {
+        parent_directories: true,
+        ..Default::default()
+    };
+    let result = lint_with_options(archive, options);
+    assert!(result.issues.contains(&Issue::MissingParentDirectory));
+    assert_eq!(EXIT_POLICY, result.exit_code());
+}
+
+#[test]
+fn fatal_warning() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("a", b'0', 0), b"");
+    entry(&mut archive, header("a", b'0', 0), b"");
+    finish_record(&mut archive);
+    let result = lint(archive);
+    assert!(result.records.is_none());
+    assert_eq!(Severity::Warning, Issue::DuplicatePath.severity());
+    assert_eq!(EXIT_ISSUES, result.exit_code());
+}
+
+#[test]
+fn fatal_error() {
+    let mut block = header("a", b'0', 0);
+    block[0] = b'b';
+    let mut archive = Vec::new();
+    entry(&mut archive, block, b"");
+    finish_record(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::InvalidChecksum));
+    assert_eq!(EXIT_ERRORS, result.exit_code());
+}
+
+struct Reject;
+
+impl Rule for Reject {
+    fn entry(&mut self, _header: &LintHeader, _offset: usize) -> Vec<Violation> {
+        vec![Violation::new("REJECT", "Entry rejected.").with_severity(Severity::Error)]
+    }
+}
+
+#[test]
+fn rule_error() {
+    let mut bytes = Vec::new();
+    entry(&mut bytes, header("a", b'0', 0), b"");
+    finish_record(&mut bytes);
+    let mut archive = Archive::new(Box::new(Cursor::new(bytes)));
+    archive.add_rule(Box::new(Reject));
+    assert_eq!(EXIT_ERRORS, archive.lint().unwrap().exit_code());
+}
+
+#[test]
+fn truncated_hidden_archive() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("a", b'0', 1), b"a");
+    finish(&mut archive);
+    entry(&mut archive, header("b", b'0', 1), b"b");
+    archive.extend_from_slice(&header("c", b'0', 1024));
+    assert_eq!(EXIT_ERRORS, lint(archive).exit_code());
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use alquitran::issues::Issue;
+use common::entry;
+use common::finish;
+use common::header;
+use common::link;
+use common::lint;
+
+#[test]
+fn symlink_to_later_entry() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("d/", b'5', 0), b"");
+    entry(&mut archive, link("d/l", b'2', "../f"), b"");
+    entry(&mut archive, header("f", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.is_portable());
+    assert!(result.links.is_empty());
+}
+
+#[test]
+fn symlink_escapes_root() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("d/", b'5', 0), b"");
+    entry(&mut archive, link("d/l", b'2', "../../etc"), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::SymlinkEscapesRoot));
+    assert_eq!(1, result.links.len());
+    assert_eq!(("d/l".to_string(), Some(1)), result.links[0].chain[0]);
+}
+
+#[test]
+fn symlink_cycle() {
+    let mut archive = Vec::new();
+    entry(&mut archive, link("a", b'2', "b"), b"");
+    entry(&mut archive, link("b", b'2', "a"), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::SymlinkCycle));
+    assert_eq!(2, result.links.len());
+}
+
+#[test]
+fn hard_link_to_preceding_file() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("f", b'0', 0), b"");
+    entry(&mut archive, link("l", b'1', "f"), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.is_portable());
+}
+
+#[test]
+fn hard_link_to_following_file() {
+    let mut archive = Vec::new();
+    entry(&mut archive, link("l", b'1', "f"), b"");
+    entry(&mut archive, header("f", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::HardLinkTargetMissing));
+}
+
+#[test]
+fn hard_link_to_directory() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("d/", b'5', 0), b"");
+    entry(&mut archive, link("l", b'1', "d"), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::HardLinkToDirectory));
+    let chain = &result.links[0].chain;
+    assert_eq!(("l".to_string(), Some(1)), chain[0]);
+    assert_eq!(("d".to_string(), Some(0)), chain[1]);
+}
+
+#[test]
+fn hard_link_to_link() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("f", b'0', 0), b"");
+    entry(&mut archive, link("l", b'1', "f"), b"");
+    entry(&mut archive, link("m", b'1', "l"), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::HardLinkToLink));
+}
+
+#[test]
+fn hard_link_not_normalized() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("d/f", b'0', 0), b"");
+    entry(&mut archive, link("l", b'1', "./d//f"), b"");
+    entry(&mut archive, header("m", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::HardLinkNotNormalized));
+    assert_eq!(vec!["d/f", "l", "m"], result.entries);
+    assert!(result.records.is_some());
+}
+
+#[test]
+fn hard_link_with_dot_prefix() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("./d/", b'5', 0), b"");
+    entry(&mut archive, header("./d/f", b'0', 0), b"");
+    entry(&mut archive, link("./d/l", b'1', "./d/f"), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.is_portable());
+    assert!(result.links.is_empty());
+}
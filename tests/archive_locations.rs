@@ -0,0 +1,203 @@
+mod common;
+
+use alquitran::header::BLOCK_SIZE;
+use alquitran::header::FIELDS;
+use alquitran::header::GNU_FIELDS;
+use alquitran::header::GNU_SPARSE_EXTENDED;
+use alquitran::header::LINKNAME_RANGE;
+use alquitran::header::MAGIC_RANGE;
+use alquitran::header::MODE_RANGE;
+use alquitran::header::UID_RANGE;
+use alquitran::header::V7_FIELDS;
+use alquitran::header::VERSION_RANGE;
+use alquitran::header::field_name;
+use alquitran::issues::Issue;
+use alquitran::options::Options;
+use common::entry;
+use common::finish;
+use common::header;
+use common::link;
+use common::lint;
+use common::lint_with_options;
+use common::pax;
+use common::set_checksum;
+use common::set_number;
+
+#[test]
+fn field_names() {
+    let block = header("a", b'0', 0);
+    assert_eq!(Some("name"), field_name(&block, 0));
+    assert_eq!(Some("uid"), field_name(&block, UID_RANGE.end - 1));
+    assert_eq!(Some("prefix"), field_name(&block, GNU_SPARSE_EXTENDED));
+    assert_eq!(Some("padding"), field_name(&block, BLOCK_SIZE - 1));
+    assert_eq!(None, field_name(&block, BLOCK_SIZE));
+}
+
+#[test]
+fn gnu_field_names() {
+    let mut block = header("a", b'S', 0);
+    block[MAGIC_RANGE.start..VERSION_RANGE.end].copy_from_slice(b"ustar  \0");
+    assert_eq!(Some("atime"), field_name(&block, 345));
+    assert_eq!(Some("sparse"), field_name(&block, 400));
+    assert_eq!(Some("isextended"), field_name(&block, GNU_SPARSE_EXTENDED));
+    assert_eq!(Some("realsize"), field_name(&block, 483));
+}
+
+#[test]
+fn v7_field_names() {
+    let mut block = header("a", b'0', 0);
+    block[MAGIC_RANGE.start..].fill(0);
+    assert_eq!(Some("linkname"), field_name(&block, LINKNAME_RANGE.start));
+    assert_eq!(Some("padding"), field_name(&block, MAGIC_RANGE.start));
+}
+
+#[test]
+fn fields_cover_header() {
+    for table in [&FIELDS[..], &GNU_FIELDS[..], &V7_FIELDS[..]] {
+        let mut end = 0;
+        for (_, range) in table.iter() {
+            assert_eq!(end, range.start);
+            end = range.end;
+        }
+        assert_eq!(BLOCK_SIZE, end);
+    }
+}
+
+#[test]
+fn unterminated_uid() {
+    let mut block = header("b", b'0', 0);
+    block[UID_RANGE].fill(b'0');
+    set_checksum(&mut block);
+    let mut archive = Vec::new();
+    entry(&mut archive, header("a", b'0', 3), b"abc");
+    entry(&mut archive, block, b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert_eq!(1, result.findings.len());
+    let finding = &result.findings[0];
+    assert_eq!(Issue::UnterminatedNumber, finding.issue);
+    assert_eq!("b", finding.path);
+    assert_eq!(1, finding.entry);
+    assert_eq!(Some("uid"), finding.field);
+    let start = 2 * BLOCK_SIZE + UID_RANGE.start;
+    assert_eq!(start..start + UID_RANGE.len(), finding.location);
+    assert_eq!(1, result.dumps.len());
+    assert_eq!(2, result.dump.unwrap().offset);
+}
+
+#[test]
+fn dump_per_block() {
+    let mut archive = Vec::new();
+    for path in ["a", "b"] {
+        let mut block = header(path, b'0', 0);
+        set_number(&mut block, MODE_RANGE, 0o4644);
+        set_checksum(&mut block);
+        entry(&mut archive, block, b"");
+    }
+    finish(&mut archive);
+    let options = Options {
+        special_mode_bits: true,
+        ..Default::default()
+    };
+    let result = lint_with_options(archive, options);
+    assert_eq!(2, result.findings.len());
+    assert_eq!(1, result.findings[1].entry);
+    assert_eq!(Some("mode"), result.findings[1].field);
+    let offsets: Vec<usize> = result.dumps.iter().map(|d| d.offset).collect();
+    assert_eq!(vec![0, 1], offsets);
+    assert_eq!(0, result.dump.unwrap().offset);
+}
+
+#[test]
+fn data_padding() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("a", b'0', 3), b"abcd");
+    finish(&mut archive);
+    let result = lint(archive);
+    let finding = &result.findings[0];
+    assert_eq!(Issue::DataPaddingNotNul, finding.issue);
+    assert_eq!(None, finding.field);
+    assert_eq!(BLOCK_SIZE + 3..2 * BLOCK_SIZE, finding.location);
+    assert_eq!(1, result.dump.unwrap().offset);
+}
+
+#[test]
+fn pax_record() {
+    let mut archive = Vec::new();
+    pax(&mut archive, "PaxHeaders/a", &[("uid", "1"), ("uid", "2")]);
+    entry(&mut archive, header("a", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert_eq!(1, result.findings.len());
+    let finding = &result.findings[0];
+    assert_eq!(Issue::PaxHeaderKeywordDuplicate, finding.issue);
+    assert_eq!("PaxHeaders/a", finding.path);
+    assert_eq!(None, finding.field);
+    assert_eq!(BLOCK_SIZE + 8..BLOCK_SIZE + 16, finding.location);
+    assert_eq!(1, result.dump.unwrap().offset);
+}
+
+#[test]
+fn pax_volatile_time() {
+    let mut archive = Vec::new();
+    pax(
+        &mut archive,
+        "PaxHeaders/a",
+        &[("atime", "1"), ("uid", "1")],
+    );
+    entry(&mut archive, header("a", b'0', 0), b"");
+    finish(&mut archive);
+    let options = Options {
+        volatile_times: true,
+        ..Default::default()
+    };
+    let result = lint_with_options(archive, options);
+    assert_eq!(1, result.findings.len());
+    let finding = &result.findings[0];
+    assert_eq!(Issue::PaxVolatileTime, finding.issue);
+    assert_eq!(None, finding.field);
+    assert_eq!(BLOCK_SIZE..BLOCK_SIZE + 11, finding.location);
+    let offsets: Vec<usize> = result.dumps.iter().map(|d| d.offset).collect();
+    assert_eq!(vec![1], offsets);
+}
+
+#[test]
+fn duplicate_path() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("a", b'0', 0), b"");
+    entry(&mut archive, header("a", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    let finding = &result.findings[0];
+    assert_eq!(Issue::DuplicatePath, finding.issue);
+    assert_eq!(1, finding.entry);
+    assert_eq!(Some("name"), finding.field);
+}
+
+#[test]
+fn dangling_symlink() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("a", b'0', 0), b"");
+    entry(&mut archive, link("l", b'2', "missing"), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    let finding = &result.findings[0];
+    assert_eq!(Issue::SymlinkDangling, finding.issue);
+    assert_eq!(1, finding.entry);
+    assert_eq!(Some("linkname"), finding.field);
+    assert_eq!(1, result.dump.unwrap().offset);
+}
+
+#[test]
+fn divergence() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("dir/", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    let finding = result
+        .findings
+        .iter()
+        .find(|f| f.issue == Issue::ImplementationDivergence)
+        .unwrap();
+    assert_eq!(Some("typeflag"), finding.field);
+}
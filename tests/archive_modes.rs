@@ -0,0 +1,105 @@
+mod common;
+
+use alquitran::header::BLOCK_SIZE;
+use alquitran::header::MODE_RANGE;
+use alquitran::issues::Issue;
+use alquitran::lint::ERROR;
+use alquitran::options::Options;
+use common::entry;
+use common::finish;
+use common::header;
+use common::lint;
+use common::lint_with_options;
+use common::set_checksum;
+use common::set_number;
+
+fn mode(path: &str, typeflag: u8, mode: u64, size: u64) -> [u8; BLOCK_SIZE] {
+    let mut block = header(path, typeflag, size);
+    set_number(&mut block, MODE_RANGE, mode);
+    set_checksum(&mut block);
+    block
+}
+
+fn modes() -> Options {
+    Options {
+        special_mode_bits: true,
+        writable_modes: true,
+        executable_modes: true,
+        searchable_directories: true,
+        ..Options::default()
+    }
+}
+
+#[test]
+fn conforming_modes() {
+    let mut archive = Vec::new();
+    entry(&mut archive, mode("d/", b'5', 0o755, 0), b"");
+    entry(&mut archive, mode("d/f", b'0', 0o644, 3), b"abc");
+    entry(&mut archive, mode("d/s", b'0', 0o755, 9), b"#!/bin/sh");
+    finish(&mut archive);
+    let result = lint_with_options(archive, modes());
+    assert!(result.is_portable());
+}
+
+#[test]
+fn special_mode_bits() {
+    let mut archive = Vec::new();
+    entry(&mut archive, mode("d/", b'5', 0o1755, 0), b"");
+    entry(&mut archive, mode("d/f", b'0', 0o6755, 4), b"\x7fELF");
+    finish(&mut archive);
+    let result = lint_with_options(archive.clone(), modes());
+    assert!(result.issues.contains(&Issue::StickyMode));
+    assert!(result.issues.contains(&Issue::SetUidMode));
+    assert!(result.issues.contains(&Issue::SetGidMode));
+    assert_eq!(3, result.findings.len());
+    assert_eq!("d/f", result.findings[2].path);
+    assert_eq!(1, result.findings[2].offset);
+    let dump = result.dump.unwrap();
+    assert_eq!(0, dump.offset);
+    assert_eq!(ERROR, dump.marks[MODE_RANGE.start]);
+    assert!(lint(archive).is_portable());
+}
+
+#[test]
+fn writable_modes() {
+    let mut archive = Vec::new();
+    entry(&mut archive, mode("f", b'0', 0o664, 0), b"");
+    entry(&mut archive, mode("g", b'0', 0o666, 0), b"");
+    finish(&mut archive);
+    let result = lint_with_options(archive, modes());
+    assert!(result.issues.contains(&Issue::GroupWritableMode));
+    assert!(result.issues.contains(&Issue::WorldWritableMode));
+}
+
+#[test]
+fn executable_mode() {
+    let mut archive = Vec::new();
+    entry(&mut archive, mode("f", b'0', 0o755, 4), b"text");
+    finish(&mut archive);
+    let result = lint_with_options(archive, modes());
+    assert!(result.issues.contains(&Issue::ExecutableMode));
+}
+
+#[test]
+fn directory_not_searchable() {
+    let mut archive = Vec::new();
+    entry(&mut archive, mode("d/", b'5', 0o644, 0), b"");
+    finish(&mut archive);
+    let result = lint_with_options(archive, modes());
+    assert!(result.issues.contains(&Issue::DirectoryNotSearchable));
+}
+
+#[test]
+fn canonical_modes() {
+    let options = Options {
+        canonical_modes: true,
+        ..Options::default()
+    };
+    let mut archive = Vec::new();
+    entry(&mut archive, mode("d/", b'5', 0o755, 0), b"");
+    entry(&mut archive, mode("d/f", b'0', 0o600, 0), b"");
+    finish(&mut archive);
+    let result = lint_with_options(archive, options);
+    assert!(result.issues.contains(&Issue::NonCanonicalMode));
+    assert_eq!(1, result.findings.len());
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use alquitran::issues::Issue;
+use alquitran::options::Options;
+use common::entry;
+use common::finish;
+use common::header;
+use common::lint;
+use common::lint_with_options;
+
+#[test]
+fn sorted_entries() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("a/", b'5', 0), b"");
+    entry(&mut archive, header("a/b", b'0', 0), b"");
+    entry(&mut archive, header("a-b", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.order.is_sorted());
+    assert!(result.order.has_directories_first());
+}
+
+#[test]
+fn sorted_by_components() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("a/", b'5', 0), b"");
+    entry(&mut archive, header("a-b", b'0', 0), b"");
+    entry(&mut archive, header("a/b", b'0', 0), b"");
+    finish(&mut archive);
+    let options = Options {
+        sorted_entries: true,
+        ..Default::default()
+    };
+    let result = lint_with_options(archive, options);
+    assert!(result.issues.contains(&Issue::UnsortedEntries));
+    assert_eq!(
+        Some(("a-b".to_string(), "a/b".to_string())),
+        result.order.unsorted
+    );
+    assert!(result.order.has_directories_first());
+}
+
+#[test]
+fn unsorted_entries() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("c", b'0', 0), b"");
+    entry(&mut archive, header("b", b'0', 0), b"");
+    entry(&mut archive, header("a", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.is_portable());
+    assert_eq!(
+        Some(("c".to_string(), "b".to_string())),
+        result.order.unsorted
+    );
+}
+
+#[test]
+fn directory_after_contents() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("a/b", b'0', 0), b"");
+    entry(&mut archive, header("a/", b'5', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.is_portable());
+    assert_eq!(
+        Some(("a/b".to_string(), "a".to_string())),
+        result.order.misplaced
+    );
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use alquitran::header::BLOCK_SIZE;
+use alquitran::header::GID_RANGE;
+use alquitran::header::GNAME_RANGE;
+use alquitran::header::UID_RANGE;
+use alquitran::header::UNAME_RANGE;
+use alquitran::issues::Issue;
+use alquitran::options::Options;
+use common::entry;
+use common::finish;
+use common::header;
+use common::lint;
+use common::lint_with_options;
+use common::pax;
+use common::set_checksum;
+use common::set_number;
+use common::set_string;
+
+fn owner(path: &str, uid: u64, gid: u64, uname: &str, gname: &str) -> [u8; BLOCK_SIZE] {
+    let mut block = header(path, b'0', 0);
+    set_number(&mut block, UID_RANGE, uid);
+    set_number(&mut block, GID_RANGE, gid);
+    set_string(&mut block, UNAME_RANGE, uname);
+    set_string(&mut block, GNAME_RANGE, gname);
+    set_checksum(&mut block);
+    block
+}
+
+fn owners() -> Options {
+    Options {
+        numeric_owner: true,
+        anonymous_names: true,
+        consistent_owners: true,
+        large_ids: true,
+        ..Options::default()
+    }
+}
+
+#[test]
+fn root_owner() {
+    let mut archive = Vec::new();
+    entry(&mut archive, owner("a", 0, 0, "root", "root"), b"");
+    entry(&mut archive, owner("b", 0, 0, "root", "root"), b"");
+    finish(&mut archive);
+    let result = lint_with_options(archive, owners());
+    assert!(result.is_portable());
+}
+
+#[test]
+fn personal_owner() {
+    let mut archive = Vec::new();
+    entry(&mut archive, owner("a", 1000, 1000, "alice", "alice"), b"");
+    finish(&mut archive);
+    let result = lint_with_options(archive.clone(), owners());
+    assert!(result.issues.contains(&Issue::NonRootOwner));
+    assert!(result.issues.contains(&Issue::PersonalOwnerName));
+    assert!(lint(archive).is_portable());
+}
+
+#[test]
+fn inconsistent_owners() {
+    let mut archive = Vec::new();
+    entry(&mut archive, owner("a", 0, 0, "root", "root"), b"");
+    entry(&mut archive, owner("b", 0, 0, "", ""), b"");
+    finish(&mut archive);
+    let result = lint_with_options(archive, owners());
+    assert!(result.issues.contains(&Issue::InconsistentOwner));
+    assert!(result.findings.iter().all(|f| f.path == "b"));
+}
+
+#[test]
+fn large_id() {
+    let mut archive = Vec::new();
+    pax(&mut archive, "PaxHeaders/a", &[("uid", "2097152")]);
+    entry(&mut archive, header("a", b'0', 0), b"");
+    finish(&mut archive);
+    assert!(!lint(archive.clone()).issues.contains(&Issue::LargeId));
+    let options = Options {
+        large_ids: true,
+        ..Options::default()
+    };
+    let result = lint_with_options(archive, options);
+    assert!(result.issues.contains(&Issue::LargeId));
+    assert_eq!(1, result.findings.len());
+    assert_eq!("a", result.findings[0].path);
+}
+
+#[test]
+fn invalid_pax_id() {
+    for value in ["", "12a", "-1", "99999999999999999999"] {
+        let mut archive = Vec::new();
+        pax(&mut archive, "PaxHeaders/a", &[("gid", value)]);
+        entry(&mut archive, header("a", b'0', 0), b"");
+        finish(&mut archive);
+        let result = lint(archive);
+        assert!(
+            result.issues.contains(&Issue::PaxInvalidNumber),
+            "{}",
+            value
+        );
+    }
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use alquitran::archive::RECORD_SIZE;
+use alquitran::issues::Hint;
+use alquitran::issues::Issue;
+use common::entry;
+use common::finish;
+use common::header;
+use common::lint;
+
+#[test]
+fn full_record() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("file", b'0', 3), b"abc");
+    finish(&mut archive);
+    archive.resize(RECORD_SIZE as usize, 0);
+    let result = lint(archive);
+    assert!(result.is_portable());
+    assert!(result.hints.is_empty());
+    let records = result.records.unwrap();
+    assert_eq!(RECORD_SIZE, records.length);
+    assert_eq!(Some(20), records.blocking_factor());
+    assert_eq!(RECORD_SIZE - 4 * 512, records.padding);
+}
+
+#[test]
+fn partial_record() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("file", b'0', 3), b"abc");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.is_portable());
+    assert!(result.hints.contains(&Hint::PartialRecord));
+    let records = result.records.unwrap();
+    assert_eq!(Some(4), records.blocking_factor());
+    assert_eq!(0, records.padding);
+}
+
+#[test]
+fn partial_block() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("file", b'0', 3), b"abc");
+    finish(&mut archive);
+    archive.resize(RECORD_SIZE as usize + 1, 0);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::PartialBlock));
+    assert_eq!(None, result.records.unwrap().blocking_factor());
+}
+
+#[test]
+fn excess_padding() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("file", b'0', 3), b"abc");
+    finish(&mut archive);
+    archive.resize(2 * RECORD_SIZE as usize, 0);
+    let result = lint(archive);
+    assert!(result.is_portable());
+    assert!(result.hints.contains(&Hint::ExcessPadding));
+    assert!(!result.hints.contains(&Hint::PartialRecord));
+}
+
+#[test]
+fn larger_blocking_factor() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("file", b'0', 3), b"abc");
+    finish(&mut archive);
+    archive.resize(64 * 512, 0);
+    let result = lint(archive);
+    assert!(!result.hints.contains(&Hint::ExcessPadding));
+    assert_eq!(Some(64), result.records.unwrap().blocking_factor());
+}
+
+#[test]
+fn default_blocking_factor() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("file", b'0', 3), b"abc");
+    finish(&mut archive);
+    archive.resize(8 * RECORD_SIZE as usize, 0);
+    let result = lint(archive);
+    assert_eq!(Some(20), result.records.unwrap().blocking_factor());
+}
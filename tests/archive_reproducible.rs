@@ -0,0 +1,83 @@
+mod common;
+
+use alquitran::header::BLOCK_SIZE;
+use alquitran::header::MTIME_RANGE;
+use alquitran::header::UNAME_RANGE;
+use alquitran::issues::Issue;
+use alquitran::options::Options;
+use common::entry;
+use common::finish;
+use common::header;
+use common::lint_with_options;
+use common::pax;
+use common::set_checksum;
+use common::set_number;
+use common::set_string;
+
+fn reproducible() -> Options {
+    let mut options = Options::default();
+    options.reproducible();
+    options
+}
+
+fn mtime(path: &str, typeflag: u8, mtime: u64) -> [u8; BLOCK_SIZE] {
+    let mut block = header(path, typeflag, 0);
+    set_number(&mut block, MTIME_RANGE, mtime);
+    set_checksum(&mut block);
+    block
+}
+
+#[test]
+fn reproducible_archive() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("a/", b'5', 0), b"");
+    entry(&mut archive, header("a/b", b'0', 0), b"");
+    entry(&mut archive, header("a-b", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint_with_options(archive, reproducible());
+    assert!(result.is_portable());
+}
+
+#[test]
+fn unsorted_entries() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("b", b'0', 0), b"");
+    entry(&mut archive, header("a", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint_with_options(archive, reproducible());
+    assert!(result.issues.contains(&Issue::UnsortedEntries));
+    assert_eq!("a", result.findings[0].path);
+}
+
+#[test]
+fn non_uniform_timestamps() {
+    let mut archive = Vec::new();
+    entry(&mut archive, mtime("a", b'0', 1), b"");
+    entry(&mut archive, mtime("b", b'0', 2), b"");
+    finish(&mut archive);
+    let result = lint_with_options(archive, reproducible());
+    assert!(result.issues.contains(&Issue::NonUniformTimestamp));
+}
+
+#[test]
+fn owner_name() {
+    let mut block = header("a", b'0', 0);
+    set_string(&mut block, UNAME_RANGE, "root");
+    set_checksum(&mut block);
+    let mut archive = Vec::new();
+    entry(&mut archive, block, b"");
+    finish(&mut archive);
+    let result = lint_with_options(archive, reproducible());
+    assert!(result.issues.contains(&Issue::OwnerName));
+}
+
+#[test]
+fn pax_header_pid_and_times() {
+    let mut archive = Vec::new();
+    pax(&mut archive, "PaxHeaders.4242/a", &[("atime", "1")]);
+    entry(&mut archive, header("a", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint_with_options(archive, reproducible());
+    assert!(result.issues.contains(&Issue::PaxHeaderPid));
+    assert!(result.issues.contains(&Issue::PaxVolatileTime));
+}
@@ -0,0 +1,184 @@
+mod common;
+
+use alquitran::archive::Archive;
+use alquitran::header::LintHeader;
+use alquitran::header::NAME_RANGE;
+use alquitran::issues::Issue;
+use alquitran::issues::Severity;
+use alquitran::rule::Rule;
+use alquitran::rule::Violation;
+use common::entry;
+use common::finish;
+use common::header;
+use common::pax;
+use std::io::Cursor;
+
+struct NoGit;
+
+impl Rule for NoGit {
+    fn entry(&mut self, header: &LintHeader, _offset: usize) -> Vec<Violation> {
+        if header.path.split('/').any(|c| c == ".git") {
+            vec![Violation::new("NO-GIT", "Git directory in archive.").with_range(NAME_RANGE)]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[derive(Default)]
+struct License {
+    found: bool,
+}
+
+impl Rule for License {
+    fn entry(&mut self, header: &LintHeader, _offset: usize) -> Vec<Violation> {
+        self.found |= header.path == "LICENSE";
+        Vec::new()
+    }
+
+    fn end(&mut self) -> Vec<Violation> {
+        if self.found {
+            Vec::new()
+        } else {
+            vec![Violation::new("LICENSE", "No LICENSE file.")]
+        }
+    }
+}
+
+#[derive(Default)]
+struct Bytes {
+    total: usize,
+}
+
+impl Rule for Bytes {
+    fn data(&mut self, _header: &LintHeader, chunk: &[u8]) -> Vec<Violation> {
+        self.total += chunk.len();
+        if chunk.contains(&b'\r') {
+            vec![Violation::new("CRLF", "Carriage return in data.")]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn end(&mut self) -> Vec<Violation> {
+        vec![Violation::new("BYTES", &format!("{} bytes", self.total))]
+    }
+}
+
+fn lint(bytes: Vec<u8>, rules: Vec<Box<dyn Rule>>) -> alquitran::archive::ArchiveLintResult {
+    let mut archive = Archive::new(Box::new(Cursor::new(bytes)));
+    for rule in rules {
+        archive.add_rule(rule);
+    }
+    archive.lint().unwrap()
+}
+
+#[test]
+fn entry_violation() {
+    let mut bytes = Vec::new();
+    entry(&mut bytes, header(".git/", b'5', 0), b"");
+    entry(&mut bytes, header("LICENSE", b'0', 0), b"");
+    finish(&mut bytes);
+    let result = lint(bytes, vec![Box::new(NoGit), Box::new(License::default())]);
+    assert!(!result.is_portable());
+    assert_eq!(1, result.findings.len());
+    let finding = &result.findings[0];
+    assert_eq!(Issue::RuleViolation, finding.issue);
+    assert_eq!("NO-GIT", finding.code());
+    assert_eq!(Severity::Warning, finding.severity());
+    assert_eq!(".git", finding.path);
+    assert_eq!(0, finding.offset);
+    assert_eq!(Some("name"), finding.field);
+    let dump = result.dump.unwrap();
+    assert_eq!(0, dump.offset);
+    assert_ne!(0, dump.marks[NAME_RANGE.start]);
+}
+
+#[test]
+fn archive_violation() {
+    let mut bytes = Vec::new();
+    entry(&mut bytes, header("README", b'0', 0), b"");
+    finish(&mut bytes);
+    let result = lint(bytes, vec![Box::new(License::default())]);
+    assert_eq!(1, result.findings.len());
+    assert_eq!("LICENSE", result.findings[0].code());
+    assert_eq!("", result.findings[0].path);
+    assert!(result.findings[0].location.is_empty());
+    assert!(result.dump.is_none());
+}
+
+#[test]
+fn data_chunks() {
+    let mut bytes = Vec::new();
+    pax(&mut bytes, "PaxHeaders/file", &[("uname", "root")]);
+    entry(&mut bytes, header("file", b'0', 1000), &[b'a'; 1000]);
+    entry(&mut bytes, header("dos", b'0', 2), b"\r\n");
+    finish(&mut bytes);
+    let result = lint(bytes, vec![Box::new(Bytes::default())]);
+    assert_eq!(2, result.findings.len());
+    assert_eq!("CRLF", result.findings[0].code());
+    assert_eq!("dos", result.findings[0].path);
+    assert_eq!(2, result.findings[0].entry);
+    assert_eq!("1002 bytes", result.findings[1].message());
+}
+
+#[test]
+fn without_rules() {
+    let mut bytes = Vec::new();
+    entry(&mut bytes, header("README", b'0', 0), b"");
+    finish(&mut bytes);
+    let result = lint(bytes, Vec::new());
+    assert!(result.findings.is_empty());
+}
+
+#[test]
+fn severity() {
+    struct Strict;
+
+    impl Rule for Strict {
+        fn entry(&mut self, _header: &LintHeader, _offset: usize) -> Vec<Violation> {
+            vec![Violation::new("STRICT", "Entry found.").with_severity(Severity::Error)]
+        }
+    }
+
+    let mut bytes = Vec::new();
+    entry(&mut bytes, header("README", b'0', 0), b"");
+    finish(&mut bytes);
+    let result = lint(bytes, vec![Box::new(Strict)]);
+    assert_eq!(Severity::Error, result.findings[0].severity());
+    assert_eq!("[STRICT] Entry found.", result.findings[0].to_string());
+}
+
+#[test]
+fn concatenated_archive() {
+    let mut bytes = Vec::new();
+    entry(&mut bytes, header("README", b'0', 0), b"");
+    finish(&mut bytes);
+    entry(&mut bytes, header(".git/", b'5', 0), b"");
+    finish(&mut bytes);
+    let result = lint(bytes, vec![Box::new(NoGit)]);
+    assert!(result.findings.is_empty());
+    let concatenated = result.concatenated.unwrap();
+    assert_eq!(1, concatenated.findings.len());
+    assert_eq!("NO-GIT", concatenated.findings[0].code());
+    assert_eq!(3, concatenated.findings[0].offset);
+}
+
+#[test]
+fn range_beyond_header() {
+    struct Wide;
+
+    impl Rule for Wide {
+        fn entry(&mut self, _header: &LintHeader, _offset: usize) -> Vec<Violation> {
+            vec![Violation::new("WIDE", "Range beyond header.").with_range(500..600)]
+        }
+    }
+
+    let mut bytes = Vec::new();
+    entry(&mut bytes, header("README", b'0', 0), b"");
+    finish(&mut bytes);
+    let result = lint(bytes, vec![Box::new(Wide)]);
+    assert_eq!(1, result.findings.len());
+    let marks = &result.dump.unwrap().marks;
+    assert!(marks[500..].iter().all(|&m| m != 0));
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use alquitran::header::DEVMAJOR_RANGE;
+use alquitran::header::DEVMINOR_RANGE;
+use alquitran::issues::Issue;
+use alquitran::options::Options;
+use common::entry;
+use common::finish;
+use common::header;
+use common::lint;
+use common::lint_with_options;
+use common::set_checksum;
+use common::set_number;
+
+fn special_files() -> Options {
+    Options {
+        special_files: true,
+        ..Options::default()
+    }
+}
+
+#[test]
+fn character_device() {
+    let mut block = header("null", b'3', 0);
+    set_number(&mut block, DEVMAJOR_RANGE, 1);
+    set_number(&mut block, DEVMINOR_RANGE, 3);
+    set_checksum(&mut block);
+    let mut archive = Vec::new();
+    entry(&mut archive, block, b"");
+    finish(&mut archive);
+    assert!(lint(archive.clone()).is_portable());
+    let result = lint_with_options(archive, special_files());
+    assert_eq!(1, result.issues.len());
+    assert!(result.issues.contains(&Issue::SpecialFile));
+    assert_eq!("null", result.findings[0].path);
+}
+
+#[test]
+fn large_device_numbers() {
+    let mut block = header("dev", b'3', 0);
+    set_number(&mut block, DEVMAJOR_RANGE, 4096);
+    set_number(&mut block, DEVMINOR_RANGE, 1048575);
+    set_checksum(&mut block);
+    let mut archive = Vec::new();
+    entry(&mut archive, block, b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::LargeDeviceNumber));
+    assert_eq!(1, result.findings.len());
+    assert_eq!(Some("devmajor"), result.findings[0].field);
+}
+
+#[test]
+fn block_device_without_numbers() {
+    let mut block = header("sda", b'4', 0);
+    block[DEVMAJOR_RANGE].fill(0);
+    set_checksum(&mut block);
+    let mut archive = Vec::new();
+    entry(&mut archive, block, b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::NoNumber));
+}
+
+#[test]
+fn regular_file_with_device_numbers() {
+    let mut block = header("file", b'0', 0);
+    set_number(&mut block, DEVMAJOR_RANGE, 1);
+    set_checksum(&mut block);
+    let mut archive = Vec::new();
+    entry(&mut archive, block, b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::DevMajorWithoutSpecialFile));
+}
+
+#[test]
+fn fifo() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("fifo", b'6', 0), b"");
+    finish(&mut archive);
+    let result = lint_with_options(archive, special_files());
+    assert!(result.issues.contains(&Issue::SpecialFile));
+}
+
+#[test]
+fn contiguous_file() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("file", b'7', 3), b"abc");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::ContiguousFile));
+    assert_eq!(Some("typeflag"), result.findings[0].field);
+    assert_eq!(vec!["file"], result.entries);
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use alquitran::header::BLOCK_SIZE;
+use alquitran::header::MTIME_RANGE;
+use alquitran::issues::Hint;
+use alquitran::issues::Issue;
+use alquitran::options::Options;
+use common::entry;
+use common::finish;
+use common::header;
+use common::lint;
+use common::lint_with_options;
+use common::pax;
+use common::set_checksum;
+use common::set_number;
+
+fn mtime(path: &str, mtime: u64) -> [u8; BLOCK_SIZE] {
+    let mut block = header(path, b'0', 0);
+    set_number(&mut block, MTIME_RANGE, mtime);
+    set_checksum(&mut block);
+    block
+}
+
+#[test]
+fn sane_timestamps() {
+    let options = Options {
+        timestamps: true,
+        ..Options::default()
+    };
+    let mut archive = Vec::new();
+    entry(&mut archive, mtime("a", 1700000000), b"");
+    finish(&mut archive);
+    let result = lint_with_options(archive, options);
+    assert!(result.is_portable());
+    assert!(!result.hints.contains(&Hint::FractionalTimestamp));
+}
+
+#[test]
+fn insane_timestamps() {
+    let options = Options {
+        timestamps: true,
+        ..Options::default()
+    };
+    let mut archive = Vec::new();
+    entry(&mut archive, mtime("a", 0), b"");
+    entry(&mut archive, mtime("b", 0o70000000000), b"");
+    finish(&mut archive);
+    let result = lint_with_options(archive.clone(), options);
+    assert!(result.issues.contains(&Issue::ZeroTimestamp));
+    assert!(result.issues.contains(&Issue::FutureTimestamp));
+    assert!(lint(archive).is_portable());
+}
+
+#[test]
+fn large_timestamp() {
+    let mut archive = Vec::new();
+    pax(&mut archive, "PaxHeaders/a", &[("mtime", "8589934592")]);
+    entry(&mut archive, mtime("a", 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::LargeTimestamp));
+}
+
+#[test]
+fn fractional_timestamp() {
+    let mut archive = Vec::new();
+    pax(&mut archive, "PaxHeaders/a", &[("mtime", "1700000000.5")]);
+    entry(&mut archive, mtime("a", 1700000000), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.is_portable());
+    assert!(result.hints.contains(&Hint::FractionalTimestamp));
+}
+
+#[test]
+fn invalid_pax_timestamp() {
+    for value in ["1700000000.5x", "1.7e9", ".5"] {
+        let mut archive = Vec::new();
+        pax(&mut archive, "PaxHeaders/a", &[("mtime", value)]);
+        entry(&mut archive, mtime("a", 1700000000), b"");
+        finish(&mut archive);
+        let result = lint(archive);
+        assert!(
+            result.issues.contains(&Issue::PaxInvalidNumber),
+            "{}",
+            value
+        );
+    }
+}
+
+#[test]
+fn source_date_epoch() {
+    let options = Options {
+        source_date_epoch: Some(1700000000),
+        ..Options::default()
+    };
+    let mut archive = Vec::new();
+    entry(&mut archive, mtime("a", 1600000000), b"");
+    entry(&mut archive, mtime("b", 1700000000), b"");
+    entry(&mut archive, mtime("c", 1700000001), b"");
+    finish(&mut archive);
+    let result = lint_with_options(archive, options);
+    assert!(result.issues.contains(&Issue::UnclampedTimestamp));
+    assert_eq!(1, result.findings.len());
+    assert_eq!("c", result.findings[0].path);
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use alquitran::header::BLOCK_SIZE;
+use alquitran::header::GNU_SPARSE_EXTENDED;
+use alquitran::header::GNU_SPARSE_EXTENSION_EXTENDED;
+use alquitran::header::MAGIC_RANGE;
+use alquitran::issues::Issue;
+use common::entry;
+use common::finish;
+use common::header;
+use common::lint;
+use common::set_checksum;
+
+fn gnu(path: &str, typeflag: u8, size: u64) -> [u8; BLOCK_SIZE] {
+    let mut block = header(path, typeflag, size);
+    block[MAGIC_RANGE.start..MAGIC_RANGE.start + 8].copy_from_slice(b"ustar  \0");
+    set_checksum(&mut block);
+    block
+}
+
+#[test]
+fn long_name() {
+    let name = format!("{}/file", "d".repeat(120));
+    let mut data = name.clone().into_bytes();
+    data.push(0);
+    let mut archive = Vec::new();
+    entry(
+        &mut archive,
+        gnu("././@LongLink", b'L', data.len() as u64),
+        &data,
+    );
+    entry(&mut archive, gnu(&name[..100], b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert_eq!(1, result.issues.len());
+    assert!(result.issues.contains(&Issue::GnuLongName));
+    assert_eq!(vec![name], result.entries);
+}
+
+#[test]
+fn long_name_with_dot_dot() {
+    let mut archive = Vec::new();
+    entry(
+        &mut archive,
+        gnu("././@LongLink", b'L', 11),
+        b"../escaped\0",
+    );
+    entry(&mut archive, gnu("escaped", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::DotDotPath));
+}
+
+#[test]
+fn long_name_duplicates_plain_entry() {
+    let mut archive = Vec::new();
+    entry(&mut archive, gnu("file", b'0', 0), b"");
+    entry(&mut archive, gnu("././@LongLink", b'L', 7), b"./file\0");
+    entry(&mut archive, gnu("other", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.duplicated_paths.contains("file"));
+}
+
+#[test]
+fn sparse_extension_blocks() {
+    let mut block = gnu("sparse", b'S', 3);
+    block[GNU_SPARSE_EXTENDED] = 1;
+    set_checksum(&mut block);
+    let mut extension = [0; BLOCK_SIZE];
+    extension[GNU_SPARSE_EXTENSION_EXTENDED] = 1;
+    let mut archive = Vec::new();
+    archive.extend_from_slice(&block);
+    archive.extend_from_slice(&extension);
+    extension[GNU_SPARSE_EXTENSION_EXTENDED] = 0;
+    entry(&mut archive, extension, b"abc");
+    entry(&mut archive, gnu("file", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert_eq!(1, result.issues.len());
+    assert!(result.issues.contains(&Issue::GnuSparse));
+    assert_eq!(vec!["sparse", "file"], result.entries);
+}
+
+#[test]
+fn dump_directory() {
+    let mut archive = Vec::new();
+    entry(&mut archive, gnu("dir/", b'D', 5), b"Yfile");
+    entry(&mut archive, gnu("dir/file", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert_eq!(1, result.issues.len());
+    assert!(result.issues.contains(&Issue::GnuDumpDir));
+}
+
+#[test]
+fn inode_without_data() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("file", b'I', 1024), b"");
+    entry(&mut archive, header("next", b'0', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert_eq!(1, result.issues.len());
+    assert!(result.issues.contains(&Issue::SolarisInode));
+}
+
+#[test]
+fn unknown_uppercase() {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("file", b'Z', 0), b"");
+    finish(&mut archive);
+    let result = lint(archive);
+    assert!(result.issues.contains(&Issue::InvalidTypeFlag));
+}
@@ -0,0 +1,95 @@
+#![allow(dead_code)]
+
+use alquitran::archive::Archive;
+use alquitran::archive::ArchiveLintResult;
+use alquitran::header::BLOCK_SIZE;
+use alquitran::header::CKSUM_RANGE;
+use alquitran::header::DEVMAJOR_RANGE;
+use alquitran::header::DEVMINOR_RANGE;
+use alquitran::header::GID_RANGE;
+use alquitran::header::LINKNAME_RANGE;
+use alquitran::header::MAGIC_RANGE;
+use alquitran::header::MODE_RANGE;
+use alquitran::header::MTIME_RANGE;
+use alquitran::header::NAME_RANGE;
+use alquitran::header::SIZE_RANGE;
+use alquitran::header::TYPEFLAG_RANGE;
+use alquitran::header::UID_RANGE;
+use alquitran::options::Options;
+use core::ops::Range;
+use std::io::Cursor;
+
+pub fn header(path: &str, typeflag: u8, size: u64) -> [u8; BLOCK_SIZE] {
+    let mut block = [0; BLOCK_SIZE];
+    block[NAME_RANGE.start..NAME_RANGE.start + path.len()].copy_from_slice(path.as_bytes());
+    let mode = if typeflag == b'5' { 0o755 } else { 0o644 };
+    set_number(&mut block, MODE_RANGE, mode);
+    set_number(&mut block, UID_RANGE, 0);
+    set_number(&mut block, GID_RANGE, 0);
+    set_number(&mut block, SIZE_RANGE, size);
+    set_number(&mut block, MTIME_RANGE, 0o14000000000);
+    block[TYPEFLAG_RANGE.start] = typeflag;
+    block[MAGIC_RANGE.start..MAGIC_RANGE.start + 8].copy_from_slice(b"ustar\x0000");
+    set_number(&mut block, DEVMAJOR_RANGE, 0);
+    set_number(&mut block, DEVMINOR_RANGE, 0);
+    set_checksum(&mut block);
+    block
+}
+
+pub fn link(path: &str, typeflag: u8, linkname: &str) -> [u8; BLOCK_SIZE] {
+    let mut block = header(path, typeflag, 0);
+    set_string(&mut block, LINKNAME_RANGE, linkname);
+    set_checksum(&mut block);
+    block
+}
+
+pub fn set_number(block: &mut [u8; BLOCK_SIZE], range: Range<usize>, value: u64) {
+    let width = range.len() - 1;
+    let digits = format!("{:0width$o}\0", value, width = width);
+    block[range].copy_from_slice(digits.as_bytes());
+}
+
+pub fn set_string(block: &mut [u8; BLOCK_SIZE], range: Range<usize>, value: &str) {
+    block[range.clone()].fill(0);
+    block[range.start..range.start + value.len()].copy_from_slice(value.as_bytes());
+}
+
+pub fn set_checksum(block: &mut [u8; BLOCK_SIZE]) {
+    block[CKSUM_RANGE].fill(b' ');
+    let sum: u64 = block.iter().map(|&b| b as u64).sum();
+    let digits = format!("{:06o}\0 ", sum);
+    block[CKSUM_RANGE].copy_from_slice(digits.as_bytes());
+}
+
+pub fn entry(archive: &mut Vec<u8>, block: [u8; BLOCK_SIZE], data: &[u8]) {
+    archive.extend_from_slice(&block);
+    archive.extend_from_slice(data);
+    archive.resize(archive.len().next_multiple_of(BLOCK_SIZE), 0);
+}
+
+pub fn finish(archive: &mut Vec<u8>) {
+    archive.resize(archive.len() + 2 * BLOCK_SIZE, 0);
+}
+
+pub fn lint(archive: Vec<u8>) -> ArchiveLintResult {
+    Archive::new(Box::new(Cursor::new(archive))).lint().unwrap()
+}
+
+pub fn lint_with_options(archive: Vec<u8>, options: Options) -> ArchiveLintResult {
+    Archive::with_options(Box::new(Cursor::new(archive)), options)
+        .lint()
+        .unwrap()
+}
+
+pub fn pax(archive: &mut Vec<u8>, path: &str, records: &[(&str, &str)]) {
+    let mut data = Vec::new();
+    for (keyword, value) in records {
+        let record = format!(" {}={}\n", keyword, value);
+        let mut length = record.len();
+        while length != record.len() + length.to_string().len() {
+            length = record.len() + length.to_string().len();
+        }
+        data.extend_from_slice(format!("{}{}", length, record).as_bytes());
+    }
+    entry(archive, header(path, b'x', data.len() as u64), &data);
+}
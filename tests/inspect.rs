@@ -0,0 +1,45 @@
+mod common;
+
+use common::entry;
+use common::finish;
+use common::header;
+use std::io::Write;
+use std::process::Command;
+use std::process::Stdio;
+
+/// Runs inspect on archive with NO_COLOR unset and returns standard output.
+fn inspect(archive: &[u8], args: &[&str]) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_alquitran"))
+        .arg("inspect")
+        .args(args)
+        .env_remove("NO_COLOR")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(archive).unwrap();
+    let output = child.wait_with_output().unwrap();
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn padded() -> Vec<u8> {
+    let mut archive = Vec::new();
+    entry(&mut archive, header("a", b'0', 3), b"abcd");
+    finish(&mut archive);
+    archive
+}
+
+#[test]
+fn color_auto_without_terminal() {
+    let output = inspect(&padded(), &["--data", "--entry", "0"]);
+    assert!(output.contains("Data (3 bytes):"));
+    assert!(!output.contains('\x1b'));
+    assert!(output.lines().any(|l| l.trim_start().starts_with("^^ ")));
+}
+
+#[test]
+fn color_always() {
+    let output = inspect(&padded(), &["--color=always", "--data", "--entry", "0"]);
+    assert!(output.contains("\x1b["));
+}
@@ -0,0 +1,44 @@
+use alquitran::issues::Category;
+use alquitran::issues::Issue;
+use alquitran::issues::Severity;
+
+#[test]
+fn codes_are_sequential() {
+    for (n, issue) in Issue::ALL.iter().enumerate() {
+        assert_eq!(format!("AQ{:04}", n + 1), issue.code());
+        assert_eq!(Some(*issue), Issue::from_code(&issue.code()));
+    }
+}
+
+#[test]
+fn stable_codes() {
+    assert_eq!("AQ0001", Issue::AbsolutePath.code());
+    assert_eq!("AQ0010", Issue::InvalidChecksum.code());
+    assert_eq!("AQ0041", Issue::PaxHeaderKeywordNoUtf8.code());
+}
+
+#[test]
+fn unknown_code() {
+    assert_eq!(None, Issue::from_code("AQ0000"));
+    assert_eq!(None, Issue::from_code("checksum"));
+}
+
+#[test]
+fn display() {
+    assert_eq!(
+        "[AQ0010] Checksum does not match.",
+        Issue::InvalidChecksum.to_string()
+    );
+}
+
+#[test]
+fn metadata() {
+    assert_eq!(Severity::Error, Issue::DotDotPath.severity());
+    assert_eq!(Category::PathSafety, Issue::DotDotPath.category());
+    assert_eq!(Category::Pax, Issue::PaxEmpty.category());
+    assert!(Issue::InvalidMagic.reference().is_some());
+    assert!(Issue::UnsortedEntries.reference().is_none());
+    for issue in Issue::ALL.iter() {
+        assert!(!issue.explanation().is_empty());
+    }
+}
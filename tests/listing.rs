@@ -0,0 +1,173 @@
+mod common;
+
+use alquitran::archive::Archive;
+use alquitran::entry::Entry;
+use alquitran::header::BLOCK_SIZE;
+use alquitran::header::DEVMAJOR_RANGE;
+use alquitran::header::DEVMINOR_RANGE;
+use alquitran::header::LintHeader;
+use alquitran::header::MAGIC_RANGE;
+use alquitran::header::MODE_RANGE;
+use alquitran::header::MTIME_RANGE;
+use alquitran::header::NAME_RANGE;
+use alquitran::header::TYPEFLAG_RANGE;
+use alquitran::header::UID_RANGE;
+use alquitran::header::UNAME_RANGE;
+use alquitran::issues::Finding;
+use alquitran::issues::Issue;
+use alquitran::listing::Visitor;
+use alquitran::listing::decode_field;
+use alquitran::listing::format_time;
+use alquitran::listing::list_entry;
+use alquitran::listing::mode_string;
+use alquitran::options::Options;
+use common::entry;
+use common::finish;
+use common::header;
+use common::link;
+use common::set_checksum;
+use common::set_number;
+use common::set_string;
+use core::ops::Range;
+use std::io::Cursor;
+
+#[test]
+fn modes() {
+    assert_eq!("-rw-r--r--", mode_string(b'0', 0o644));
+    assert_eq!("drwxr-xr-x", mode_string(b'5', 0o755));
+    assert_eq!("-rwsr-Sr-x", mode_string(b'0', 0o6745));
+    assert_eq!("drwxrwxrwt", mode_string(b'5', 0o1777));
+    assert_eq!("xrw-r--r--", mode_string(b'x', 0o644));
+}
+
+#[test]
+fn times() {
+    assert_eq!("1970-01-01 00:00", format_time(0));
+    assert_eq!("2000-02-29 23:59", format_time(951868799));
+    assert_eq!("2038-01-19 03:14", format_time(0x7fffffff));
+}
+
+#[test]
+fn regular_file() {
+    let mut block = header("dir/file", b'0', 1234);
+    set_string(&mut block, UNAME_RANGE, "root");
+    set_checksum(&mut block);
+    let header = LintHeader::new(block);
+    assert_eq!(
+        "-rw-r--r-- root/0         1234 2021-01-14 08:25 dir/file",
+        list_entry(&header)
+    );
+}
+
+#[test]
+fn links() {
+    let header = LintHeader::new(link("a", b'2', "b"));
+    assert!(list_entry(&header).ends_with(" a -> b"));
+    let header = LintHeader::new(link("a", b'1', "b"));
+    assert!(list_entry(&header).ends_with(" a link to b"));
+}
+
+#[test]
+fn device() {
+    let mut block = header("null", b'3', 0);
+    set_number(&mut block, DEVMAJOR_RANGE, 1);
+    set_number(&mut block, DEVMINOR_RANGE, 3);
+    set_checksum(&mut block);
+    let header = LintHeader::new(block);
+    assert!(list_entry(&header).starts_with("crw-r--r-- 0/0             1,3 "));
+}
+
+#[test]
+fn decoded_fields() {
+    let block = header("dir/", b'5', 0);
+    let decode = |name: &str, range: Range<usize>| decode_field(name, &block[range]);
+    assert_eq!(Some("'dir/'".to_string()), decode("name", NAME_RANGE));
+    assert_eq!(Some("0755".to_string()), decode("mode", MODE_RANGE));
+    assert_eq!(Some("0".to_string()), decode("uid", UID_RANGE));
+    assert_eq!(
+        Some("1610612736 (2021-01-14 08:25)".to_string()),
+        decode("mtime", MTIME_RANGE)
+    );
+    assert_eq!(Some("'5'".to_string()), decode("typeflag", TYPEFLAG_RANGE));
+    assert_eq!(Some("'ustar'".to_string()), decode("magic", MAGIC_RANGE));
+    assert_eq!(None, decode("atime", 345..357));
+    assert_eq!(
+        Some("0 (1970-01-01 00:00)".to_string()),
+        decode_field("atime", b"00000000000\0")
+    );
+    assert_eq!(None, decode("padding", 500..512));
+    assert_eq!(Some("invalid".to_string()), decode_field("size", b"12x\0"));
+}
+
+#[test]
+fn visitor_findings() {
+    struct Collect(Vec<(usize, Vec<Issue>)>);
+
+    impl Visitor for Collect {
+        fn checked(&mut self, entry: &Entry, findings: &[Finding]) {
+            let issues = findings.iter().map(|f| f.issue).collect();
+            self.0.push((entry.offset, issues));
+        }
+
+        fn resolved(&mut self, findings: &[Finding]) {
+            for finding in findings {
+                self.0.push((finding.offset, vec![finding.issue]));
+            }
+        }
+    }
+
+    let mut bytes = Vec::new();
+    let mut block = header("a", b'0', 0);
+    set_number(&mut block, MODE_RANGE, 0o4644);
+    set_checksum(&mut block);
+    entry(&mut bytes, header("b", b'0', 0), b"");
+    entry(&mut bytes, block, b"");
+    entry(&mut bytes, link("l", b'2', "missing"), b"");
+    finish(&mut bytes);
+    entry(&mut bytes, header("c", b'0', 0), b"");
+    finish(&mut bytes);
+    let options = Options {
+        special_mode_bits: true,
+        sorted_entries: true,
+        ..Default::default()
+    };
+    let mut archive = Archive::with_options(Box::new(Cursor::new(bytes)), options);
+    let mut visitor = Collect(Vec::new());
+    let result = archive.visit(&mut visitor).unwrap();
+    assert_eq!(3, result.findings.len());
+    let expected = vec![
+        (0, vec![]),
+        (1, vec![Issue::SetUidMode, Issue::UnsortedEntries]),
+        (2, vec![]),
+        (2, vec![Issue::SymlinkDangling]),
+        (5, vec![]),
+    ];
+    assert_eq!(expected, visitor.0);
+}
+
+#[test]
+fn visitor_blocks() {
+    struct Dump(Vec<(usize, u8)>);
+
+    impl Visitor for Dump {
+        fn entry(&mut self, entry: &Entry) -> bool {
+            entry.header.path == "b"
+        }
+
+        fn block(&mut self, offset: usize, block: &[u8; BLOCK_SIZE]) {
+            self.0.push((offset, block[BLOCK_SIZE - 1]));
+        }
+    }
+
+    let mut bytes = Vec::new();
+    entry(&mut bytes, header("a", b'0', 3), b"abc");
+    let mut data = vec![b'x'; 600];
+    data.resize(1024, b'p');
+    entry(&mut bytes, header("b", b'0', 600), &data);
+    finish(&mut bytes);
+    let mut archive = Archive::new(Box::new(Cursor::new(bytes)));
+    let mut visitor = Dump(Vec::new());
+    let result = archive.visit(&mut visitor).unwrap();
+    assert_eq!(vec![(3, b'x'), (4, b'p')], visitor.0);
+    assert!(result.issues.contains(&Issue::DataPaddingNotNul));
+}